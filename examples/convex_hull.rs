@@ -1,13 +1,15 @@
+use std::sync::Arc;
+
 use lazy_reach::{
-    convex::SHalfspacePolytope, lazy_operation::ConvexHull, lazy_operation::LinearTransformation,
-    overapproximate,
+    convex::SHalfspacePolytope, error::SetError, lazy_operation::ConvexHull,
+    lazy_operation::LinearTransformation, overapproximate,
 };
 use nalgebra::{SMatrix, SVector};
 
 const DIM: usize = 2;
 type Float = f32;
 
-fn main() {
+fn main() -> Result<(), SetError> {
     let rot = SMatrix::<Float, 2, 2>::from_vec(vec![
         Float::cos(0.4),
         -Float::sin(0.4),
@@ -19,16 +21,16 @@ fn main() {
         SMatrix::<Float, 4, 2>::from_vec(vec![1.0, -1.0, 0.0, 0.0, 0.0, 0.0, 1.0, -1.0]);
     let h = SVector::<Float, 4>::from_vec(vec![1.0, 1.0, 1.0, 1.0]);
     let polytope = SHalfspacePolytope::<Float, 4, 2>::new(a_transform, h);
-    let lt = LinearTransformation::<Float, DIM>::new(rot, Box::new(polytope.clone()));
+    let lt = LinearTransformation::<Float, DIM>::new(rot, Arc::new(polytope));
 
     let a_transform2 =
         SMatrix::<Float, 4, 2>::from_vec(vec![1.0, -1.0, 0.0, 0.0, 0.0, 0.0, 1.0, -1.0]);
     let h2 = SVector::<Float, 4>::from_vec(vec![4.0, -2.0, 4.0, -2.0]);
     let polytope1 = SHalfspacePolytope::<Float, 4, 2>::new(a_transform2, h2);
 
-    let ch = ConvexHull::<Float, DIM>::new(Box::new(lt), Box::new(polytope1));
+    let ch = ConvexHull::<Float, DIM>::new(Arc::new(lt), Arc::new(polytope1));
 
-    let oa = overapproximate::overapproximate::<Float, DIM>(&ch, 20);
+    let oa = overapproximate::overapproximate::<Float, DIM>(&ch, 20)?;
 
     // print the oa a_tranform and upperbounds as csv
     for i in 0..oa.a_transform.nrows() {
@@ -38,4 +40,6 @@ fn main() {
         print!("{}", oa.upper_bounds[i]);
         println!();
     }
+
+    Ok(())
 }
@@ -1,17 +1,15 @@
+use std::sync::Arc;
+
 use lazy_reach::{
-    convex::Hypersphere,
-    convex::SHalfspacePolytope,
-    convex::Singleton,
-    lazy_operation::MinkowskiSum,
-    lazy_operation::{ConvexHull, LinearTransformation},
-    overapproximate,
+    convex::Hypersphere, convex::SHalfspacePolytope, convex::Singleton, error::SetError,
+    lazy_operation::LinearTransformation, lazy_operation::MinkowskiSum, overapproximate,
 };
 use nalgebra::{SMatrix, SVector};
 
 const DIM: usize = 2;
 type Float = f32;
 
-fn main() {
+fn main() -> Result<(), SetError> {
     let rot = SMatrix::<Float, 2, 2>::from_vec(vec![
         Float::cos(0.4),
         -Float::sin(0.4),
@@ -26,19 +24,19 @@ fn main() {
 
     let polytope = SHalfspacePolytope::<Float, 4, 2>::new(a_transform, h);
 
-    let lt = LinearTransformation::<Float, DIM>::new(rot, Box::new(polytope.clone()));
+    let lt = LinearTransformation::<Float, DIM>::new(rot, Arc::new(polytope));
 
     let sphere =
         Hypersphere::<Float, DIM>::new(2.0, SVector::<Float, DIM>::from_vec(vec![5.0, 5.0]));
 
     // create minkowski sum
-    let ms = MinkowskiSum::<Float, DIM>::new(Box::new(sphere), Box::new(lt));
+    let ms = MinkowskiSum::<Float, DIM>::new(Arc::new(sphere), Arc::new(lt));
 
     let s = Singleton::<Float, DIM>::new(SVector::<Float, DIM>::from_vec(vec![0.0, 0.0]));
 
-    let ch = MinkowskiSum::<Float, DIM>::new(Box::new(ms), Box::new(s));
+    let ch = MinkowskiSum::<Float, DIM>::new(Arc::new(ms), Arc::new(s));
 
-    let oa = overapproximate::overapproximate::<Float, DIM>(&ch, 80);
+    let oa = overapproximate::overapproximate::<Float, DIM>(&ch, 80)?;
 
     // print the oa a_tranform and upperbounds as csv
     for i in 0..oa.a_transform.nrows() {
@@ -48,4 +46,6 @@ fn main() {
         print!("{}", oa.upper_bounds[i]);
         println!();
     }
+
+    Ok(())
 }
@@ -0,0 +1,691 @@
+/*!
+ * Geometric predicates on lazy sets.
+ */
+use std::cmp::Ordering;
+
+use nalgebra::{Const, DMatrix, DVector, Dynamic, RealField, SVector};
+use num_traits::ToPrimitive;
+
+use crate::convex::{
+    within_bound, DHalfspacePolytope, DLazySet, DirectionMatrix, Hyperrectangle, Hypersphere,
+    LazySet, Singleton,
+};
+use crate::error::SetError;
+use crate::lazy_operation::intersection::{is_disjoint_from_halfspaces, stack_constraints};
+use crate::lp::{LpSolver, MiniLp};
+
+/// The maximum number of support evaluations of the separating direction search.
+const MAX_ITERATIONS: usize = 512;
+
+/// Searches for a direction d with d^T point > rho(d), which proves that the point lies outside
+/// of the set with support function `support`.
+///
+/// Wolfe's minimum norm point algorithm moves the point x of the convex hull of a few support points
+/// nearest to the point towards it, querying the support function in the direction point - x and
+/// dropping support points whose weight vanishes. Unlike steps along single segments this doesn't
+/// zig-zag on flat faces and is exact for polytopes. The search ends without a separating direction
+/// when x comes within a small tolerance of the point, when the set is unbounded in the search
+/// direction or after the maximum number of iterations, so only `Some` is conclusive.
+pub(crate) fn separating_direction<N, F>(
+    support: F,
+    point: &DVector<N>,
+) -> Result<Option<DVector<N>>, SetError>
+where
+    N: RealField + Copy,
+    F: Fn(&DVector<N>) -> Result<(N, DVector<N>), SetError>,
+{
+    let tol = N::default_epsilon().sqrt() * (N::one() + point.norm());
+    let mut points = match support(&DVector::zeros(point.len())) {
+        Ok((_, x)) => vec![x],
+        Err(SetError::Unbounded) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let mut weights = vec![N::one()];
+    let mut x = points[0].clone();
+
+    for _ in 0..MAX_ITERATIONS {
+        let d = point - &x;
+        if d.norm() <= tol {
+            return Ok(None);
+        }
+        let (rho, s) = match support(&d) {
+            Ok(support) => support,
+            Err(SetError::Unbounded) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        // the hyperplane d^T y = rho(d) separates the point from the set
+        if d.dot(point) > rho + tol * d.norm() {
+            return Ok(Some(d));
+        }
+        // x is nearest to the point up to the tolerance if s is already known
+        if points.iter().any(|p| (p - &s).norm() <= tol) {
+            return Ok(None);
+        }
+        points.push(s);
+        weights.push(N::zero());
+
+        // move towards the point of the affine hull nearest to the point, dropping the support
+        // points whose weight vanishes on the way until it lies in the convex hull
+        loop {
+            let Some(affine) = affine_weights(&points, point) else {
+                return Ok(None);
+            };
+            let blocking = (0..points.len())
+                .filter(|&i| affine[i] < N::zero())
+                .map(|i| (weights[i] / (weights[i] - affine[i]), i))
+                .min_by(|(s, _), (t, _)| s.partial_cmp(t).unwrap_or(Ordering::Equal));
+            match blocking {
+                Some((step, dropped)) => {
+                    for (w, a) in weights.iter_mut().zip(&affine) {
+                        *w += (*a - *w) * step;
+                    }
+                    weights[dropped] = N::zero();
+                }
+                None => weights = affine,
+            }
+            (points, weights) = points
+                .into_iter()
+                .zip(weights)
+                .filter(|(_, w)| *w > N::zero())
+                .unzip();
+            if blocking.is_none() {
+                break;
+            }
+        }
+        x = points
+            .iter()
+            .zip(&weights)
+            .fold(DVector::zeros(point.len()), |sum, (p, w)| sum + p * *w);
+    }
+    Ok(None)
+}
+
+/// The weights summing to one of the point in the affine hull of the points nearest to the given
+/// point, or `None` if the points are affinely dependent.
+fn affine_weights<N>(points: &[DVector<N>], point: &DVector<N>) -> Option<Vec<N>>
+where
+    N: RealField + Copy,
+{
+    // minimize |sum_i w_i (p_i - point)|^2 subject to sum_i w_i = 1 by its KKT system
+    let count = points.len();
+    let offsets: Vec<_> = points.iter().map(|p| p - point).collect();
+    let system = DMatrix::<N>::from_fn(count + 1, count + 1, |i, j| match (i < count, j < count) {
+        (true, true) => offsets[i].dot(&offsets[j]),
+        (false, false) => N::zero(),
+        _ => N::one(),
+    });
+    let rhs = DVector::<N>::from_fn(
+        count + 1,
+        |i, _| if i < count { N::zero() } else { N::one() },
+    );
+    let solution = system.lu().solve(&rhs)?;
+    let weights: Vec<N> = solution.iter().take(count).copied().collect();
+    weights.iter().all(|w| w.is_finite()).then_some(weights)
+}
+
+/// Returns true if the two sets have no point in common.
+///
+/// Polyhedral sets are checked exactly by the feasibility LP of their stacked constraints. A set
+/// intersected with a polyhedral one is checked by the multipliers of its constraints, as in the
+/// support function of an [`Intersection`](crate::lazy_operation::Intersection). Other sets are
+/// checked by searching for a hyperplane separating them, with support queries on the difference
+/// X - Y. In the last two cases `true` is always correct while sets that touch within a small
+/// tolerance, or for which the search is inconclusive, are reported as intersecting. See
+/// [`IsDisjoint`] for exact checks between spheres, boxes, singletons and halfspace polytopes.
+pub fn is_disjoint<N, const D: usize>(lhs: &dyn LazySet<N, D>, rhs: &dyn LazySet<N, D>) -> bool
+where
+    N: RealField + Copy + ToPrimitive,
+{
+    is_disjoint_with_solver(lhs, rhs, MiniLp)
+}
+
+/// Returns true if the two sets have no point in common like [`is_disjoint`], solving the LP of
+/// polyhedral sets with the given solver.
+pub fn is_disjoint_with_solver<N, S, const D: usize>(
+    lhs: &dyn LazySet<N, D>,
+    rhs: &dyn LazySet<N, D>,
+    solver: S,
+) -> bool
+where
+    N: RealField + Copy + ToPrimitive,
+    S: LpSolver,
+{
+    if lhs.is_empty() || rhs.is_empty() {
+        return true;
+    }
+    match (lhs.constraints(), rhs.constraints()) {
+        (Some(lhs), Some(rhs)) => {
+            let (a_transform, b) = stack_constraints::<N, D>(lhs, rhs);
+            return DHalfspacePolytope::new(a_transform, b)
+                .with_solver(solver)
+                .is_empty();
+        }
+        // the search below gives up on the unbounded halfspaces, so use the dual of the
+        // intersection with them
+        (Some((a_transform, b)), None) => {
+            return is_disjoint_from_halfspaces(rhs, &a_transform, &b, &solver)
+        }
+        (None, Some((a_transform, b))) => {
+            return is_disjoint_from_halfspaces(lhs, &a_transform, &b, &solver)
+        }
+        (None, None) => {}
+    }
+
+    // 0 lies outside of X - Y exactly when X and Y are disjoint
+    let support = |d: &DVector<N>| {
+        let d = SVector::<N, D>::from_column_slice(d.as_slice());
+        let (rho1, p1) = lhs.try_support(&d)?;
+        let (rho2, p2) = rhs.try_support(&-d)?;
+        Ok((
+            rho1 + rho2,
+            DVector::from_column_slice((p1 - p2).as_slice()),
+        ))
+    };
+    matches!(
+        separating_direction(support, &DVector::zeros(D)),
+        Ok(Some(_)) | Err(SetError::Infeasible)
+    )
+}
+
+/// The result of checking whether a set is contained in another set.
+#[derive(Clone, Debug)]
+pub enum Inclusion<N, const D: usize> {
+    /// The set is contained in the other set.
+    Included,
+    /// The set is not contained in the other set, witnessed by a point of the set that lies
+    /// outside of the other set.
+    NotIncluded(SVector<N, D>),
+}
+
+impl<N, const D: usize> Inclusion<N, D> {
+    /// Returns true if the set is contained in the other set.
+    pub fn is_included(&self) -> bool {
+        matches!(self, Inclusion::Included)
+    }
+
+    /// The point of the set outside of the other set, if there is one.
+    pub fn witness(&self) -> Option<&SVector<N, D>> {
+        match self {
+            Inclusion::Included => None,
+            Inclusion::NotIncluded(point) => Some(point),
+        }
+    }
+}
+
+/// Checks whether a set is contained in a polyhedral set, with one support query per constraint row
+/// of [`LazySet::constraints`].
+///
+/// A row a^T x <= b is violated if the support of the set in the direction a exceeds b by more
+/// than a small tolerance, and the support point is returned as the witness. Reports
+/// [`SetError::Unbounded`] if the set is unbounded in the direction of a row, in which case it is
+/// not contained in the polyhedral set but there is no support point to witness it, and
+/// [`SetError::NotPolyhedral`] if the other set has no constraints.
+pub fn is_subset<N, const D: usize>(
+    set: &dyn LazySet<N, D>,
+    polyhedron: &dyn LazySet<N, D>,
+) -> Result<Inclusion<N, D>, SetError>
+where
+    N: RealField + Copy,
+{
+    let (a_transform, upper_bounds) = polyhedron.constraints().ok_or(SetError::NotPolyhedral)?;
+    if a_transform.ncols() != D {
+        return Err(SetError::DimensionMismatch {
+            expected: D,
+            found: a_transform.ncols(),
+        });
+    }
+    if upper_bounds.len() != a_transform.nrows() {
+        return Err(SetError::DimensionMismatch {
+            expected: a_transform.nrows(),
+            found: upper_bounds.len(),
+        });
+    }
+
+    let directions = DirectionMatrix::<N, D>::from_fn_generic(
+        Const::<D>,
+        Dynamic::new(a_transform.nrows()),
+        |i, j| a_transform[(j, i)],
+    );
+    for (result, b) in set
+        .try_support_batch(&directions)
+        .into_iter()
+        .zip(upper_bounds.iter())
+    {
+        match result {
+            Ok((rho, point)) => {
+                if !within_bound(rho, *b) {
+                    return Ok(Inclusion::NotIncluded(point));
+                }
+            }
+            // the empty set is contained in every set
+            Err(SetError::Infeasible) => return Ok(Inclusion::Included),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(Inclusion::Included)
+}
+
+/// Exact inclusion checks between pairs of sets of known types.
+pub trait IsSubset<N, const D: usize, Rhs> {
+    /// Checks whether the set is contained in `other`.
+    fn is_subset(&self, other: &Rhs) -> Inclusion<N, D>;
+}
+
+impl<N, const D: usize> IsSubset<N, D, Hyperrectangle<N, D>> for Hyperrectangle<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_subset(&self, other: &Hyperrectangle<N, D>) -> Inclusion<N, D> {
+        if self.is_empty() {
+            return Inclusion::Included;
+        }
+        within_box(self, other)
+    }
+}
+
+impl<N, const D: usize> IsSubset<N, D, Hypersphere<N, D>> for Hyperrectangle<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_subset(&self, other: &Hypersphere<N, D>) -> Inclusion<N, D> {
+        if self.is_empty() {
+            return Inclusion::Included;
+        }
+        point_inclusion(farthest_vertex(self, &other.center), other)
+    }
+}
+
+impl<N, const D: usize> IsSubset<N, D, Singleton<N, D>> for Hyperrectangle<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_subset(&self, other: &Singleton<N, D>) -> Inclusion<N, D> {
+        if self.is_empty() {
+            return Inclusion::Included;
+        }
+        point_inclusion(farthest_vertex(self, &other.point), other)
+    }
+}
+
+impl<N, const D: usize> IsSubset<N, D, Hyperrectangle<N, D>> for Hypersphere<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_subset(&self, other: &Hyperrectangle<N, D>) -> Inclusion<N, D> {
+        if self.is_empty() {
+            return Inclusion::Included;
+        }
+        within_box(self, other)
+    }
+}
+
+impl<N, const D: usize> IsSubset<N, D, Hypersphere<N, D>> for Hypersphere<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_subset(&self, other: &Hypersphere<N, D>) -> Inclusion<N, D> {
+        if self.is_empty() {
+            return Inclusion::Included;
+        }
+        point_inclusion(farthest_point(self, &other.center), other)
+    }
+}
+
+impl<N, const D: usize> IsSubset<N, D, Singleton<N, D>> for Hypersphere<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_subset(&self, other: &Singleton<N, D>) -> Inclusion<N, D> {
+        if self.is_empty() {
+            return Inclusion::Included;
+        }
+        point_inclusion(farthest_point(self, &other.point), other)
+    }
+}
+
+impl<N, const D: usize> IsSubset<N, D, Hyperrectangle<N, D>> for Singleton<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_subset(&self, other: &Hyperrectangle<N, D>) -> Inclusion<N, D> {
+        point_inclusion(self.point, other)
+    }
+}
+
+impl<N, const D: usize> IsSubset<N, D, Hypersphere<N, D>> for Singleton<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_subset(&self, other: &Hypersphere<N, D>) -> Inclusion<N, D> {
+        point_inclusion(self.point, other)
+    }
+}
+
+impl<N, const D: usize> IsSubset<N, D, Singleton<N, D>> for Singleton<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_subset(&self, other: &Singleton<N, D>) -> Inclusion<N, D> {
+        point_inclusion(self.point, other)
+    }
+}
+
+/// Exact disjointness checks between pairs of sets of known types.
+///
+/// Unlike [`is_disjoint`] these compare the sets without tolerance, so sets that are apart by any
+/// positive distance are reported as disjoint and sets that touch as intersecting.
+pub trait IsDisjoint<Rhs> {
+    /// Returns true if the set has no point in common with `other`.
+    fn is_disjoint(&self, other: &Rhs) -> bool;
+}
+
+impl<N, const D: usize> IsDisjoint<Hyperrectangle<N, D>> for Hyperrectangle<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_disjoint(&self, other: &Hyperrectangle<N, D>) -> bool {
+        self.is_empty()
+            || other.is_empty()
+            || (0..D).any(|i| {
+                (self.center[i] - other.center[i]).abs() > self.radius[i] + other.radius[i]
+            })
+    }
+}
+
+impl<N, const D: usize> IsDisjoint<Hypersphere<N, D>> for Hyperrectangle<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_disjoint(&self, other: &Hypersphere<N, D>) -> bool {
+        self.is_empty() || other.is_empty() || box_distance(self, &other.center) > other.radius
+    }
+}
+
+impl<N, const D: usize> IsDisjoint<Singleton<N, D>> for Hyperrectangle<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_disjoint(&self, other: &Singleton<N, D>) -> bool {
+        self.is_empty() || box_distance(self, &other.point) > N::zero()
+    }
+}
+
+impl<N, const D: usize> IsDisjoint<Hyperrectangle<N, D>> for Hypersphere<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_disjoint(&self, other: &Hyperrectangle<N, D>) -> bool {
+        other.is_disjoint(self)
+    }
+}
+
+impl<N, const D: usize> IsDisjoint<Hypersphere<N, D>> for Hypersphere<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_disjoint(&self, other: &Hypersphere<N, D>) -> bool {
+        self.is_empty()
+            || other.is_empty()
+            || (self.center - other.center).norm() > self.radius + other.radius
+    }
+}
+
+impl<N, const D: usize> IsDisjoint<Singleton<N, D>> for Hypersphere<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_disjoint(&self, other: &Singleton<N, D>) -> bool {
+        self.is_empty() || (other.point - self.center).norm() > self.radius
+    }
+}
+
+impl<N, const D: usize> IsDisjoint<Hyperrectangle<N, D>> for Singleton<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_disjoint(&self, other: &Hyperrectangle<N, D>) -> bool {
+        other.is_disjoint(self)
+    }
+}
+
+impl<N, const D: usize> IsDisjoint<Hypersphere<N, D>> for Singleton<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_disjoint(&self, other: &Hypersphere<N, D>) -> bool {
+        other.is_disjoint(self)
+    }
+}
+
+impl<N, const D: usize> IsDisjoint<Singleton<N, D>> for Singleton<N, D>
+where
+    N: RealField + Copy,
+{
+    fn is_disjoint(&self, other: &Singleton<N, D>) -> bool {
+        self.point != other.point
+    }
+}
+
+/// Checked by the feasibility LP of the stacked constraints with the solver of `self`, so the
+/// result is exact up to the tolerance of the solver. Polytopes of different dimensions have no
+/// point in common.
+impl<N, S, T> IsDisjoint<DHalfspacePolytope<N, T>> for DHalfspacePolytope<N, S>
+where
+    N: RealField + Copy + ToPrimitive,
+    S: LpSolver + Clone,
+{
+    fn is_disjoint(&self, other: &DHalfspacePolytope<N, T>) -> bool {
+        let dim = self.a_transform.ncols();
+        if other.a_transform.ncols() != dim {
+            return true;
+        }
+        let rows = self.a_transform.nrows();
+        let a_transform = DMatrix::from_fn(rows + other.a_transform.nrows(), dim, |i, j| {
+            if i < rows {
+                self.a_transform[(i, j)]
+            } else {
+                other.a_transform[(i - rows, j)]
+            }
+        });
+        let mut upper_bounds = self.upper_bounds.clone();
+        upper_bounds.extend(other.upper_bounds.iter().copied());
+        DHalfspacePolytope::new(a_transform, upper_bounds)
+            .with_solver(self.solver().clone())
+            .is_empty()
+    }
+}
+
+/// The Euclidean distance of a point from a box.
+fn box_distance<N, const D: usize>(
+    hyperrectangle: &Hyperrectangle<N, D>,
+    point: &SVector<N, D>,
+) -> N
+where
+    N: RealField + Copy,
+{
+    SVector::<N, D>::from_fn(|i, _| {
+        ((point[i] - hyperrectangle.center[i]).abs() - hyperrectangle.radius[i]).max(N::zero())
+    })
+    .norm()
+}
+
+/// The inclusion of a set whose point farthest from the other set is `point`.
+fn point_inclusion<N, const D: usize>(
+    point: SVector<N, D>,
+    other: &dyn LazySet<N, D>,
+) -> Inclusion<N, D>
+where
+    N: RealField,
+{
+    if other.is_member(&point) {
+        Inclusion::Included
+    } else {
+        Inclusion::NotIncluded(point)
+    }
+}
+
+/// Checks a bounded set against the faces of a box, with the support point in the normal
+/// direction of a violated face as the witness.
+fn within_box<N, const D: usize>(
+    set: &dyn LazySet<N, D>,
+    other: &Hyperrectangle<N, D>,
+) -> Inclusion<N, D>
+where
+    N: RealField + Copy,
+{
+    let (low, high) = (other.low(), other.high());
+    for i in 0..D {
+        let (rho, point) = set.support(&unit(i, N::one()));
+        if !within_bound(rho, high[i]) {
+            return Inclusion::NotIncluded(point);
+        }
+        let (rho, point) = set.support(&unit(i, -N::one()));
+        if !within_bound(rho, -low[i]) {
+            return Inclusion::NotIncluded(point);
+        }
+    }
+    Inclusion::Included
+}
+
+/// The vertex of a box farthest from a point.
+fn farthest_vertex<N, const D: usize>(
+    hyperrectangle: &Hyperrectangle<N, D>,
+    point: &SVector<N, D>,
+) -> SVector<N, D>
+where
+    N: RealField + Copy,
+{
+    SVector::from_fn(|i, _| {
+        let (c, r) = (hyperrectangle.center[i], hyperrectangle.radius[i]);
+        if c >= point[i] {
+            c + r
+        } else {
+            c - r
+        }
+    })
+}
+
+/// The point of a sphere farthest from a point.
+fn farthest_point<N, const D: usize>(
+    sphere: &Hypersphere<N, D>,
+    point: &SVector<N, D>,
+) -> SVector<N, D>
+where
+    N: RealField + Copy,
+{
+    let offset = sphere.center - point;
+    let norm = offset.norm();
+    if norm.is_zero() {
+        // every point of the boundary is equally far
+        if D == 0 {
+            return sphere.center;
+        }
+        return sphere.center + unit(0, sphere.radius);
+    }
+    sphere.center + offset * (sphere.radius / norm)
+}
+
+/// The multiple `value` of the i-th unit vector.
+fn unit<N, const D: usize>(i: usize, value: N) -> SVector<N, D>
+where
+    N: RealField + Copy,
+{
+    SVector::from_fn(|j, _| if j == i { value } else { N::zero() })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use nalgebra::{DMatrix, DVector, Matrix2, Vector2};
+
+    use super::{is_disjoint, is_subset, IsDisjoint, IsSubset};
+    use crate::convex::{
+        DHalfspacePolytope, HalfSpace, Hyperrectangle, Hypersphere, LazySet, SHalfspacePolytope,
+        Singleton,
+    };
+    use crate::error::SetError;
+    use crate::lazy_operation::MinkowskiSum;
+
+    #[test]
+    fn rounded_box_membership() {
+        let square = Hyperrectangle::new(Vector2::zeros(), Vector2::new(1.0, 1.0));
+        let disk = Hypersphere::new(1.0, Vector2::zeros());
+        let rounded = MinkowskiSum::new(Arc::new(square), Arc::new(disk));
+        assert!(!rounded.is_member(&Vector2::new(2.01, 0.0)));
+        assert!(rounded.is_member(&Vector2::new(1.99, 0.0)));
+        assert!(rounded.is_member(&Vector2::new(2.0, 0.5)));
+    }
+
+    #[test]
+    fn disk_beside_halfspace() {
+        let halfspace = HalfSpace::new(Vector2::new(1.0, 0.0), 0.0);
+        let far = Hypersphere::new(1.0, Vector2::new(5.0, 5.0));
+        let near = Hypersphere::new(1.0, Vector2::new(0.5, 5.0));
+        assert!(is_disjoint(&far, &halfspace));
+        assert!(is_disjoint(&halfspace, &far));
+        assert!(!is_disjoint(&near, &halfspace));
+    }
+
+    #[test]
+    fn disk_touching_box() {
+        let disk = Hypersphere::new(1.0, Vector2::new(0.1, 0.0));
+        let square = Hyperrectangle::from_bounds(Vector2::new(-0.9, -1.0), Vector2::new(1.1, 1.0));
+        assert!(disk.is_subset(&square).is_included());
+        assert!(is_subset(&disk, &square).unwrap().is_included());
+    }
+
+    #[test]
+    fn subset_of_static_polytope() {
+        let disk = Hypersphere::new(1.0, Vector2::zeros());
+        let wedge =
+            SHalfspacePolytope::new(Matrix2::new(1.0, 1.0, 1.0, -1.0), Vector2::new(2.0, 1.0));
+        let inclusion = is_subset(&disk, &wedge).unwrap();
+        assert!(!inclusion.is_included());
+        assert!(!wedge.is_member(inclusion.witness().unwrap()));
+        assert_eq!(
+            is_subset(&wedge, &disk).unwrap_err(),
+            SetError::NotPolyhedral
+        );
+    }
+
+    #[test]
+    fn nearly_touching_spheres() {
+        let sphere = Hypersphere::new(1.0, Vector2::zeros());
+        let apart = Hypersphere::new(1.0, Vector2::new(2.0 + 1e-9, 0.0));
+        let touching = Hypersphere::new(1.0, Vector2::new(2.0, 0.0));
+        assert!(sphere.is_disjoint(&apart));
+        assert!(!sphere.is_disjoint(&touching));
+        assert!(Singleton::new(Vector2::new(1.0 + 1e-9, 0.0)).is_disjoint(&sphere));
+        assert!(!Singleton::new(Vector2::new(1.0, 0.0)).is_disjoint(&sphere));
+    }
+
+    #[test]
+    fn sphere_beside_box_corner() {
+        let square = Hyperrectangle::new(Vector2::zeros(), Vector2::new(1.0, 1.0));
+        let corner = 1.0 + 0.5f64.sqrt();
+        let apart = Hypersphere::new(1.0 - 1e-9, Vector2::new(corner, corner));
+        let overlapping = Hypersphere::new(1.0 + 1e-9, Vector2::new(corner, corner));
+        assert!(square.is_disjoint(&apart));
+        assert!(apart.is_disjoint(&square));
+        assert!(!square.is_disjoint(&overlapping));
+        assert!(!square.is_disjoint(&Singleton::new(Vector2::new(1.0, -1.0))));
+        assert!(square.is_disjoint(&Hyperrectangle::new(
+            Vector2::new(0.0, 2.0 + 1e-9),
+            Vector2::new(1.0, 1.0)
+        )));
+    }
+
+    #[test]
+    fn separated_halfspace_polytopes() {
+        let square = |x: f64| {
+            DHalfspacePolytope::new(
+                DMatrix::from_row_slice(4, 2, &[1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0]),
+                DVector::from_column_slice(&[x + 1.0, 1.0, 1.0 - x, 1.0]),
+            )
+        };
+        assert!(square(0.0).is_disjoint(&square(3.0)));
+        assert!(!square(0.0).is_disjoint(&square(1.5)));
+        assert!(!square(0.0).is_disjoint(&square(2.0)));
+    }
+}
@@ -0,0 +1,464 @@
+/*!
+ * Conversion between the halfspace and vertex representations of polytopes.
+ */
+use nalgebra::{DMatrix, DVector, RealField};
+use num_traits::ToPrimitive;
+
+use super::{DHalfspacePolytope, DLazySet};
+use crate::error::SetError;
+
+/// The directions e_1, ..., e_d, -e_1, ..., -e_d as the columns of a matrix.
+pub(crate) fn box_directions<N: RealField>(dimension: usize) -> DMatrix<N> {
+    DMatrix::from_fn(dimension, 2 * dimension, |i, j| {
+        if j == i {
+            N::one()
+        } else if j == i + dimension {
+            -N::one()
+        } else {
+            N::zero()
+        }
+    })
+}
+
+/// Computes the vertices of the polytope Ax <= b from its support values in the
+/// [`box_directions`], which bound the polytope.
+///
+/// Reports [`SetError::Unbounded`] for unbounded polyhedra and returns no vertices for empty ones.
+/// In two dimensions the vertices are in counter-clockwise order.
+pub(crate) fn vertices<N, I>(
+    a_transform: &DMatrix<N>,
+    upper_bounds: &DVector<N>,
+    box_supports: I,
+) -> Result<Vec<DVector<N>>, SetError>
+where
+    N: RealField + Copy,
+    I: IntoIterator<Item = Result<N, SetError>>,
+{
+    let dimension = a_transform.ncols();
+    let mut rho = Vec::with_capacity(2 * dimension);
+    for support in box_supports {
+        match support {
+            Ok(value) => rho.push(value),
+            Err(SetError::Infeasible) => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        }
+    }
+    let high = DVector::from_fn(dimension, |i, _| rho[i]);
+    let low = DVector::from_fn(dimension, |i, _| -rho[i + dimension]);
+
+    // start from a box that contains the polytope in its interior, so that the box vertices are
+    // distinct and none of them survives the cuts
+    let margin = N::one() + (&high - &low).amax();
+    let low = low.add_scalar(-margin);
+    let high = high.add_scalar(margin);
+    if dimension == 2 {
+        Ok(clip_polygon(a_transform, upper_bounds, &low, &high))
+    } else {
+        Ok(cut_box(a_transform, upper_bounds, &low, &high))
+    }
+}
+
+/// A vertex together with the indices of the constraints that are tight at it, in increasing
+/// order. The box constraints come first.
+struct Vertex<N> {
+    point: DVector<N>,
+    tight: Vec<usize>,
+}
+
+/// Cuts a box by the constraints one after another, following the double description method.
+///
+/// When a constraint cuts an edge, a new vertex is created on the edge. Two vertices span an edge
+/// if they share at least d - 1 tight constraints that are not all tight at another vertex.
+fn cut_box<N>(
+    a_transform: &DMatrix<N>,
+    upper_bounds: &DVector<N>,
+    low: &DVector<N>,
+    high: &DVector<N>,
+) -> Vec<DVector<N>>
+where
+    N: RealField + Copy,
+{
+    let dimension = low.len();
+    let box_rows = 2 * dimension;
+    let mut vertices: Vec<Vertex<N>> = (0..1usize << dimension)
+        .map(|corner| {
+            let upper = |i: usize| corner & (1 << i) != 0;
+            let mut tight: Vec<usize> = (0..dimension)
+                .map(|i| if upper(i) { i } else { i + dimension })
+                .collect();
+            tight.sort_unstable();
+            Vertex {
+                point: DVector::from_fn(dimension, |i, _| if upper(i) { high[i] } else { low[i] }),
+                tight,
+            }
+        })
+        .collect();
+
+    for k in 0..a_transform.nrows() {
+        let normal = a_transform.row(k).transpose();
+        let b = upper_bounds[k];
+        // relative to the norm of the row, so that scaled constraints cut alike
+        let tol = N::default_epsilon().sqrt() * (normal.norm() + b.abs());
+        let slack: Vec<N> = vertices.iter().map(|v| normal.dot(&v.point) - b).collect();
+
+        let mut new_vertices = Vec::new();
+        for (p, vp) in vertices.iter().enumerate() {
+            if slack[p] <= tol {
+                continue;
+            }
+            for (q, vq) in vertices.iter().enumerate() {
+                if slack[q] >= -tol {
+                    continue;
+                }
+                let common = sorted_intersection(&vp.tight, &vq.tight);
+                if common.len() + 1 < dimension {
+                    continue;
+                }
+                let shared = vertices
+                    .iter()
+                    .enumerate()
+                    .any(|(r, vr)| r != p && r != q && is_sorted_subset(&common, &vr.tight));
+                if shared {
+                    continue;
+                }
+
+                let t = slack[p] / (slack[p] - slack[q]);
+                let mut tight = common;
+                tight.push(box_rows + k);
+                new_vertices.push(Vertex {
+                    point: &vp.point + (&vq.point - &vp.point) * t,
+                    tight,
+                });
+            }
+        }
+
+        let mut kept = Vec::with_capacity(vertices.len() + new_vertices.len());
+        for (mut v, s) in vertices.into_iter().zip(slack) {
+            if s <= tol {
+                if s >= -tol {
+                    v.tight.push(box_rows + k);
+                }
+                kept.push(v);
+            }
+        }
+        kept.extend(new_vertices);
+        vertices = kept;
+    }
+    vertices.into_iter().map(|v| v.point).collect()
+}
+
+/// The elements of two increasing sequences that occur in both.
+fn sorted_intersection(lhs: &[usize], rhs: &[usize]) -> Vec<usize> {
+    let (mut i, mut j) = (0, 0);
+    let mut common = Vec::new();
+    while i < lhs.len() && j < rhs.len() {
+        if lhs[i] < rhs[j] {
+            i += 1;
+        } else if lhs[i] > rhs[j] {
+            j += 1;
+        } else {
+            common.push(lhs[i]);
+            i += 1;
+            j += 1;
+        }
+    }
+    common
+}
+
+/// Whether all elements of an increasing sequence occur in another one.
+fn is_sorted_subset(subset: &[usize], set: &[usize]) -> bool {
+    let mut set = set.iter();
+    subset.iter().all(|x| set.any(|y| y == x))
+}
+
+/// Clips a rectangle by the constraints one after another, keeping the vertices of the polygon
+/// in counter-clockwise order.
+fn clip_polygon<N>(
+    a_transform: &DMatrix<N>,
+    upper_bounds: &DVector<N>,
+    low: &DVector<N>,
+    high: &DVector<N>,
+) -> Vec<DVector<N>>
+where
+    N: RealField + Copy,
+{
+    let mut polygon = vec![
+        DVector::from_vec(vec![low[0], low[1]]),
+        DVector::from_vec(vec![high[0], low[1]]),
+        DVector::from_vec(vec![high[0], high[1]]),
+        DVector::from_vec(vec![low[0], high[1]]),
+    ];
+    for k in 0..a_transform.nrows() {
+        let normal = a_transform.row(k).transpose();
+        let b = upper_bounds[k];
+        // relative to the norm of the row, so that scaled constraints cut alike
+        let tol = N::default_epsilon().sqrt() * (normal.norm() + b.abs());
+        let slack: Vec<N> = polygon.iter().map(|p| normal.dot(p) - b).collect();
+
+        let mut clipped = Vec::with_capacity(polygon.len() + 1);
+        for i in 0..polygon.len() {
+            let j = (i + 1) % polygon.len();
+            if slack[i] <= tol {
+                clipped.push(polygon[i].clone());
+            }
+            // the edge crosses the line of the constraint
+            if (slack[i] < -tol && slack[j] > tol) || (slack[i] > tol && slack[j] < -tol) {
+                let t = slack[i] / (slack[i] - slack[j]);
+                clipped.push(&polygon[i] + (&polygon[j] - &polygon[i]) * t);
+            }
+        }
+        polygon = clipped;
+        if polygon.is_empty() {
+            break;
+        }
+    }
+
+    // drop repeated vertices left by constraints through a vertex
+    let mut vertices: Vec<DVector<N>> = Vec::with_capacity(polygon.len());
+    for p in polygon {
+        if vertices.last().map_or(true, |last| !is_near(last, &p)) {
+            vertices.push(p);
+        }
+    }
+    if vertices.len() > 1 && is_near(&vertices[0], &vertices[vertices.len() - 1]) {
+        let _ = vertices.pop();
+    }
+    vertices
+}
+
+/// Whether two points coincide up to a small tolerance.
+fn is_near<N: RealField + Copy>(p: &DVector<N>, q: &DVector<N>) -> bool {
+    (p - q).norm() <= N::default_epsilon().sqrt() * (N::one() + p.norm())
+}
+
+/// Computes the constraints `(A, b)` of the facets of the convex hull of points.
+///
+/// Lower-dimensional hulls are described by the facets within their affine hull together with
+/// pairs of opposite constraints for the directions orthogonal to it. Reports
+/// [`SetError::Infeasible`] if there are no points.
+pub(crate) fn facets<N>(points: &[DVector<N>]) -> Result<(DMatrix<N>, DVector<N>), SetError>
+where
+    N: RealField + Copy + ToPrimitive,
+{
+    let dimension = match points.first() {
+        Some(p) => p.len(),
+        None => return Err(SetError::Infeasible),
+    };
+    if let Some(p) = points.iter().find(|p| p.len() != dimension) {
+        return Err(SetError::DimensionMismatch {
+            expected: dimension,
+            found: p.len(),
+        });
+    }
+
+    let rows = if dimension == 2 {
+        hull_facets(points)
+    } else {
+        polar_facets(points)?
+    };
+    let a_transform = DMatrix::from_fn(rows.len(), dimension, |i, j| rows[i].0[j]);
+    let b = DVector::from_iterator(rows.len(), rows.iter().map(|row| row.1));
+    Ok((a_transform, b))
+}
+
+/// The affine hull of a set of points.
+pub(crate) struct AffineHull<N> {
+    /// The centroid of the points.
+    pub(crate) center: DVector<N>,
+    /// An orthonormal basis of the directions spanned by the points, one per column.
+    pub(crate) basis: DMatrix<N>,
+    /// An orthonormal basis of the orthogonal complement of the spanned directions.
+    pub(crate) orthogonal: DMatrix<N>,
+}
+
+/// Computes the affine hull of a nonempty set of points of the same dimension.
+pub(crate) fn affine_hull<N>(points: &[DVector<N>]) -> AffineHull<N>
+where
+    N: RealField + Copy,
+{
+    let dimension = points[0].len();
+    let count: N = nalgebra::convert(points.len() as f64);
+    let center = points
+        .iter()
+        .fold(DVector::zeros(dimension), |sum, p| sum + p)
+        / count;
+    let offsets: Vec<DVector<N>> = points.iter().map(|p| p - &center).collect();
+
+    // offsets left after projecting out the basis below sqrt(eps) relative to the largest offset
+    // are rounding errors of flat sets
+    let scale = offsets.iter().fold(N::zero(), |m, v| m.max(v.norm()));
+    let basis = pivoted_gram_schmidt(Vec::new(), offsets, N::default_epsilon().sqrt() * scale);
+    let rank = basis.len();
+    // the unit vectors that are farthest from the span complete the basis of the space
+    let units = (0..dimension)
+        .map(|i| DVector::from_fn(dimension, |j, _| if i == j { N::one() } else { N::zero() }))
+        .collect();
+    let complete = pivoted_gram_schmidt(basis, units, N::default_epsilon().sqrt());
+    let columns =
+        |vectors: &[DVector<N>]| DMatrix::from_fn(dimension, vectors.len(), |i, j| vectors[j][i]);
+    AffineHull {
+        basis: columns(&complete[..rank]),
+        orthogonal: columns(&complete[rank..]),
+        center,
+    }
+}
+
+/// Extends an orthonormal basis by Gram-Schmidt with column pivoting: the vector farthest from the
+/// span of the basis is added next, as long as its distance exceeds the threshold.
+fn pivoted_gram_schmidt<N>(
+    mut basis: Vec<DVector<N>>,
+    mut vectors: Vec<DVector<N>>,
+    threshold: N,
+) -> Vec<DVector<N>>
+where
+    N: RealField + Copy,
+{
+    for q in basis.iter() {
+        for v in vectors.iter_mut() {
+            let projection = q.dot(v);
+            v.axpy(-projection, q, N::one());
+        }
+    }
+    loop {
+        let Some((i, norm)) = vectors
+            .iter()
+            .map(|v| v.norm())
+            .enumerate()
+            .max_by(|(_, s), (_, t)| s.partial_cmp(t).unwrap_or(std::cmp::Ordering::Equal))
+        else {
+            return basis;
+        };
+        if norm <= threshold || norm.is_zero() {
+            return basis;
+        }
+        // orthogonalizing twice keeps the basis orthonormal when the vector is nearly dependent
+        let mut q = vectors.swap_remove(i);
+        for b in basis.iter() {
+            let projection = b.dot(&q);
+            q.axpy(-projection, b, N::one());
+        }
+        let q = q.normalize();
+        for v in vectors.iter_mut() {
+            let projection = q.dot(v);
+            v.axpy(-projection, &q, N::one());
+        }
+        basis.push(q);
+    }
+}
+
+/// The facets of the convex hull of points through the vertices of its polar.
+///
+/// Relative to an interior point c of the hull, the facets a^T (x - c) <= 1 of the hull are the
+/// vertices a of the polar polytope {a | a^T (v - c) <= 1 for all points v}.
+fn polar_facets<N>(points: &[DVector<N>]) -> Result<Vec<(DVector<N>, N)>, SetError>
+where
+    N: RealField + Copy + ToPrimitive,
+{
+    let hull = affine_hull(points);
+    let offsets = DMatrix::from_fn(hull.basis.ncols(), points.len(), |i, j| {
+        hull.basis.column(i).dot(&(&points[j] - &hull.center))
+    });
+
+    let mut rows = Vec::new();
+    if hull.basis.ncols() > 0 {
+        let polar_a = offsets.transpose();
+        let polar_b = DVector::from_element(points.len(), N::one());
+        let polar = DHalfspacePolytope::new(polar_a, polar_b);
+        let box_supports = polar
+            .try_support_batch(&box_directions(hull.basis.ncols()))
+            .into_iter()
+            .map(|support| support.map(|(value, _)| value));
+        for y in vertices(&polar.a_transform, &polar.upper_bounds, box_supports)? {
+            let normal = &hull.basis * y;
+            let b = N::one() + normal.dot(&hull.center);
+            rows.push((normal, b));
+        }
+    }
+    for normal in hull.orthogonal.column_iter() {
+        let normal = normal.into_owned();
+        let b = normal.dot(&hull.center);
+        rows.push((-&normal, -b));
+        rows.push((normal, b));
+    }
+    Ok(rows)
+}
+
+/// The vertices of the convex hull of planar points by Andrew's monotone chain, in
+/// counter-clockwise order. Points that coincide up to a small tolerance count once, and points on
+/// the edges are dropped.
+pub(crate) fn planar_hull<N>(points: &[DVector<N>]) -> Vec<&DVector<N>>
+where
+    N: RealField + Copy,
+{
+    let mut sorted: Vec<&DVector<N>> = points.iter().collect();
+    sorted.sort_by(|p, q| {
+        (p[0], p[1])
+            .partial_cmp(&(q[0], q[1]))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    sorted.dedup_by(|p, q| is_near(p, q));
+
+    // turn of the path o -> p -> q, positive for a counter-clockwise turn
+    let cross = |o: &DVector<N>, p: &DVector<N>, q: &DVector<N>| {
+        (p[0] - o[0]) * (q[1] - o[1]) - (p[1] - o[1]) * (q[0] - o[0])
+    };
+    let mut hull: Vec<&DVector<N>> = Vec::with_capacity(sorted.len() + 1);
+    for pass in [sorted.clone(), sorted.iter().rev().copied().collect()] {
+        let start = hull.len();
+        for p in pass {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= N::zero()
+            {
+                let _ = hull.pop();
+            }
+            hull.push(p);
+        }
+        // the last point of each chain starts the other one
+        let _ = hull.pop();
+    }
+    if hull.is_empty() {
+        // all points coincide
+        hull.extend(sorted.first());
+    }
+    hull
+}
+
+/// The edges of the convex hull of planar points in counter-clockwise order, see
+/// [`planar_hull`].
+fn hull_facets<N>(points: &[DVector<N>]) -> Vec<(DVector<N>, N)>
+where
+    N: RealField + Copy,
+{
+    let hull = planar_hull(points);
+    let edge_rows = |p: &DVector<N>, q: &DVector<N>| {
+        let normal = DVector::from_vec(vec![q[1] - p[1], p[0] - q[0]]);
+        let b = normal.dot(p);
+        (normal, b)
+    };
+    match hull.len() {
+        0 | 1 => {
+            // a single point, bounded along each axis
+            let p = hull[0];
+            (0..2)
+                .flat_map(|i| {
+                    let e = DVector::from_fn(2, |j, _| if j == i { N::one() } else { N::zero() });
+                    [(-&e, -p[i]), (e, p[i])]
+                })
+                .collect()
+        }
+        2 => {
+            // a segment, bounded on both sides of its line and at both ends
+            let (p, q) = (hull[0], hull[1]);
+            let along = q - p;
+            vec![
+                edge_rows(p, q),
+                edge_rows(q, p),
+                (along.clone(), along.dot(q)),
+                (-&along, -along.dot(p)),
+            ]
+        }
+        n => (0..n)
+            .map(|i| edge_rows(hull[i], hull[(i + 1) % n]))
+            .collect(),
+    }
+}
@@ -0,0 +1,76 @@
+/*!
+ * Ellipsoid
+ */
+use nalgebra::{RealField, SMatrix, SVector};
+
+use super::{within_bound, LazySet};
+
+/// An ellipsoid {x | (x - c)^T Q^{-1} (x - c) <= 1} with a positive definite shape matrix Q.
+#[derive(Clone)]
+pub struct Ellipsoid<N, const D: usize> {
+    /// The center of the ellipsoid.
+    center: SVector<N, D>,
+
+    /// The symmetric positive definite shape matrix.
+    shape: SMatrix<N, D, D>,
+
+    /// The lower triangular Cholesky factor L of the shape matrix Q = L L^T.
+    factor: SMatrix<N, D, D>,
+}
+
+impl<N, const D: usize> Ellipsoid<N, D>
+where
+    N: RealField,
+{
+    /// Create a new ellipsoid, returning `None` if the shape matrix is not symmetric positive definite.
+    pub fn new(center: SVector<N, D>, shape: SMatrix<N, D, D>) -> Option<Ellipsoid<N, D>> {
+        let symmetric = shape.relative_eq(
+            &shape.transpose(),
+            N::default_epsilon(),
+            N::default_max_relative(),
+        );
+        if !symmetric {
+            return None;
+        }
+        let factor = shape.cholesky()?.unpack();
+        Some(Ellipsoid {
+            center,
+            shape,
+            factor,
+        })
+    }
+
+    /// The center of the ellipsoid.
+    pub fn center(&self) -> &SVector<N, D> {
+        &self.center
+    }
+
+    /// The shape matrix of the ellipsoid.
+    pub fn shape(&self) -> &SMatrix<N, D, D> {
+        &self.shape
+    }
+}
+
+impl<N, const D: usize> LazySet<N, D> for Ellipsoid<N, D>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        // <d, c> + sqrt(d^T Q d), attained at c + Q d / sqrt(d^T Q d)
+        let qd = self.shape * direction;
+        let norm = direction.dot(&qd).sqrt();
+        let o = direction.dot(&self.center);
+        if norm.is_zero() {
+            return (o, self.center);
+        }
+        (o + norm, self.center + qd / norm)
+    }
+
+    fn is_member(&self, point: &SVector<N, D>) -> bool {
+        // (x - c)^T Q^{-1} (x - c) = |L^{-1} (x - c)|^2 <= 1
+        match self.factor.solve_lower_triangular(&(point - self.center)) {
+            Some(y) => within_bound(y.norm_squared(), N::one()),
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+/*!
+ * The empty set.
+ */
+use nalgebra::{DMatrix, DVector, RealField, SVector};
+
+use super::traits::empty_support;
+use super::LazySet;
+
+/// The empty set.
+#[derive(Copy, Clone, Default)]
+pub struct EmptySet;
+
+impl EmptySet {
+    /// Create a new empty set.
+    pub fn new() -> EmptySet {
+        EmptySet
+    }
+}
+
+impl<N, const D: usize> LazySet<N, D> for EmptySet
+where
+    N: RealField,
+{
+    fn support(&self, _direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        empty_support()
+    }
+
+    fn constraints(&self) -> Option<(DMatrix<N>, DVector<N>)> {
+        // 0^T x <= -1
+        Some((DMatrix::zeros(1, D), DVector::from_element(1, -N::one())))
+    }
+
+    fn is_empty(&self) -> bool {
+        true
+    }
+
+    fn is_member(&self, _point: &SVector<N, D>) -> bool {
+        false
+    }
+}
@@ -1,19 +1,82 @@
 /*!
  * Halfspace Polyhedral Sets
  */
-use minilp::{ComparisonOp, OptimizationDirection, Problem, Variable};
 use nalgebra::{DMatrix, DVector, RealField, SMatrix, SVector};
 use num_traits::ToPrimitive;
 
-use super::{DLazySet, LazySet};
+use super::conversion::{box_directions, vertices};
+use super::hyperplane::normal_multiple;
+use super::traits::{
+    d_expect_support, empty_support, expect_support, unbounded_support, within_bound,
+};
+use super::{DLazySet, DVPolytope, DirectionMatrix, LazySet, SVPolytope, Universe};
+use crate::error::SetError;
+use crate::lp::{from_f64, is_constraint_redundant, to_f64_vec, LpSolver, MiniLp};
+use crate::volume::hull_volume;
 
+/// A halfspace {x | a^T x <= b}.
 #[derive(Clone)]
-/// Dynamically allocated Halfspace polytope Hy \le h with a compact solution set
-pub struct DHalfspacePolytope<N> {
+pub struct HalfSpace<N, const D: usize> {
+    /// The outward normal vector a.
+    pub normal: SVector<N, D>,
+
+    /// The offset b.
+    pub offset: N,
+}
+
+impl<N, const D: usize> HalfSpace<N, D>
+where
+    N: RealField,
+{
+    /// Create a new halfspace.
+    pub fn new(normal: SVector<N, D>, offset: N) -> HalfSpace<N, D> {
+        HalfSpace { normal, offset }
+    }
+}
+
+impl<N, const D: usize> LazySet<N, D> for HalfSpace<N, D>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        // 0^T x <= b holds everywhere or nowhere
+        if self.normal.iter().all(|a| a.is_zero()) {
+            if self.offset >= N::zero() {
+                return Universe.support(direction);
+            }
+            return empty_support();
+        }
+        // bounded only if the direction is a nonnegative multiple of the normal
+        match normal_multiple(&self.normal, direction) {
+            Some(lambda) if lambda >= N::zero() => (
+                lambda * self.offset,
+                self.normal * (self.offset / self.normal.norm_squared()),
+            ),
+            _ => unbounded_support(direction),
+        }
+    }
+
+    fn constraints(&self) -> Option<(DMatrix<N>, DVector<N>)> {
+        Some((
+            DMatrix::<N>::from_row_slice(1, D, self.normal.as_slice()),
+            DVector::from_element(1, self.offset),
+        ))
+    }
+
+    fn is_member(&self, point: &SVector<N, D>) -> bool {
+        within_bound(self.normal.dot(point), self.offset)
+    }
+}
+
+#[derive(Clone)]
+/// Dynamically allocated Halfspace polyhedron Hy \le h, possibly unbounded or empty
+pub struct DHalfspacePolytope<N, S = MiniLp> {
     /// The linear transformation matrix.
     pub a_transform: DMatrix<N>,
     /// The upper bounds.
     pub upper_bounds: DVector<N>,
+    /// The solver for the support function LPs.
+    solver: S,
 }
 
 impl<N> DHalfspacePolytope<N> {
@@ -22,59 +85,190 @@ impl<N> DHalfspacePolytope<N> {
         DHalfspacePolytope {
             a_transform,
             upper_bounds: h,
+            solver: MiniLp,
         }
     }
 }
 
-impl<N> DLazySet<N> for DHalfspacePolytope<N>
+impl<N, S> DHalfspacePolytope<N, S> {
+    /// Use a different LP solver for the support function.
+    pub fn with_solver<T: LpSolver>(self, solver: T) -> DHalfspacePolytope<N, T> {
+        DHalfspacePolytope {
+            a_transform: self.a_transform,
+            upper_bounds: self.upper_bounds,
+            solver,
+        }
+    }
+
+    /// The solver for the support function LPs.
+    pub(crate) fn solver(&self) -> &S {
+        &self.solver
+    }
+}
+
+impl<N, S> DHalfspacePolytope<N, S>
+where
+    N: RealField + Copy + ToPrimitive,
+    S: LpSolver,
+{
+    /// The vertices of the polytope.
+    ///
+    /// The polytope is cut out of its bounding box one constraint at a time, and in two dimensions
+    /// the vertices are returned in counter-clockwise order. Reports [`SetError::Unbounded`] for
+    /// unbounded polyhedra and returns no vertices for empty ones.
+    pub fn vertices(&self) -> Result<Vec<DVector<N>>, SetError> {
+        if self.upper_bounds.len() != self.a_transform.nrows() {
+            return Err(SetError::DimensionMismatch {
+                expected: self.a_transform.nrows(),
+                found: self.upper_bounds.len(),
+            });
+        }
+        let box_supports = self
+            .try_support_batch(&box_directions(self.a_transform.ncols()))
+            .into_iter()
+            .map(|support| support.map(|(value, _)| value));
+        vertices(&self.a_transform, &self.upper_bounds, box_supports)
+    }
+
+    /// Convert the polytope to its vertex representation, following [`Self::vertices`].
+    pub fn to_vpolytope(&self) -> Result<DVPolytope<N>, SetError> {
+        Ok(DVPolytope::new(self.vertices()?))
+    }
+
+    /// The volume of the polytope from its [`Self::vertices`], following
+    /// [`DVPolytope::volume`]. Reports [`SetError::Unbounded`] for unbounded polyhedra.
+    pub fn volume(&self) -> Result<N, SetError> {
+        hull_volume(&self.vertices()?)
+    }
+
+    /// Returns the polytope without the constraints that are implied by the other ones, keeping
+    /// one of several identical constraints.
+    ///
+    /// Each constraint is checked with one LP against the constraints that are kept so far and
+    /// the ones that are still to be checked. If an LP shows that the polytope is empty, its
+    /// constraints are replaced by 0^T x <= -1.
+    pub fn remove_redundant_constraints(&self) -> Result<DHalfspacePolytope<N, S>, SetError>
+    where
+        S: Clone,
+    {
+        if self.upper_bounds.len() != self.a_transform.nrows() {
+            return Err(SetError::DimensionMismatch {
+                expected: self.a_transform.nrows(),
+                found: self.upper_bounds.len(),
+            });
+        }
+        let (a_transform, upper_bounds) =
+            irredundant_constraints(&self.solver, &self.a_transform, &self.upper_bounds)?;
+        Ok(DHalfspacePolytope {
+            a_transform,
+            upper_bounds,
+            solver: self.solver.clone(),
+        })
+    }
+}
+
+impl<N, S> DLazySet<N> for DHalfspacePolytope<N, S>
 where
     N: RealField + ToPrimitive,
+    S: LpSolver,
 {
     fn support(&self, direction: &DVector<N>) -> (N, DVector<N>) {
-        let mut problem = Problem::new(OptimizationDirection::Maximize);
-
-        // make a variable for every column in H
-        let mut vars = Vec::new();
-        for i in 0..self.a_transform.ncols() {
-            vars.push(problem.add_var(
-                direction[i].to_f64().unwrap(),
-                (f64::NEG_INFINITY, f64::INFINITY),
-            ));
-        }
+        d_expect_support(self.try_support(direction), direction)
+    }
 
-        // add constraints from H and h
-        for i in 0..self.a_transform.nrows() {
-            let mut row = Vec::<(Variable, f64)>::new();
-            for j in 0..self.a_transform.ncols() {
-                row.push((vars[j], self.a_transform[(i, j)].to_f64().unwrap()));
-            }
-            // convert row into list
-            problem.add_constraint(
-                row.as_slice(),
-                ComparisonOp::Le,
-                self.upper_bounds[i].to_f64().unwrap(),
-            );
+    fn try_support(&self, direction: &DVector<N>) -> Result<(N, DVector<N>), SetError> {
+        if direction.len() != self.a_transform.ncols() {
+            return Err(SetError::DimensionMismatch {
+                expected: self.a_transform.ncols(),
+                found: direction.len(),
+            });
         }
+        if self.upper_bounds.len() != self.a_transform.nrows() {
+            return Err(SetError::DimensionMismatch {
+                expected: self.a_transform.nrows(),
+                found: self.upper_bounds.len(),
+            });
+        }
+
+        let (objective, point) = self.solver.maximize(
+            &DVector::from_vec(to_f64_vec(direction.iter())?),
+            &DMatrix::from_vec(
+                self.a_transform.nrows(),
+                self.a_transform.ncols(),
+                to_f64_vec(self.a_transform.iter())?,
+            ),
+            &DVector::from_vec(to_f64_vec(self.upper_bounds.iter())?),
+        )?;
+
+        let values = point
+            .iter()
+            .map(|x| from_f64(*x))
+            .collect::<Result<Vec<N>, _>>()?;
+        Ok((from_f64(objective)?, DVector::from_vec(values)))
+    }
 
-        let solution = problem.solve().unwrap();
-        let objective = N::from_f64(solution.objective()).unwrap();
+    fn is_empty(&self) -> bool {
+        // the LP with a zero objective is a feasibility check
+        matches!(
+            self.try_support(&DVector::zeros(self.a_transform.ncols())),
+            Err(SetError::Infeasible)
+        )
+    }
 
-        // collect variable values into vec
-        let mut values = Vec::<N>::new();
-        for i in 0..vars.len() {
-            values.push(N::from_f64(solution[vars[i]]).unwrap());
+    fn is_member(&self, point: &DVector<N>) -> bool {
+        point.len() == self.a_transform.ncols()
+            && self.upper_bounds.len() == self.a_transform.nrows()
+            && (&self.a_transform * point)
+                .iter()
+                .zip(self.upper_bounds.iter())
+                .all(|(ax, b)| within_bound(*ax, *b))
+    }
+
+    fn try_support_batch(&self, directions: &DMatrix<N>) -> Vec<Result<(N, DVector<N>), SetError>> {
+        let dimension = self.a_transform.ncols();
+        if directions.nrows() != dimension {
+            let error = SetError::DimensionMismatch {
+                expected: dimension,
+                found: directions.nrows(),
+            };
+            return vec![Err(error); directions.ncols()];
         }
 
-        (objective, DVector::from_vec(values))
+        // convert the constraints once for all directions
+        let problem = || -> Result<_, SetError> {
+            Ok((
+                DMatrix::from_vec(
+                    dimension,
+                    directions.ncols(),
+                    to_f64_vec(directions.iter())?,
+                ),
+                DMatrix::from_vec(
+                    self.a_transform.nrows(),
+                    dimension,
+                    to_f64_vec(self.a_transform.iter())?,
+                ),
+                DVector::from_vec(to_f64_vec(self.upper_bounds.iter())?),
+            ))
+        };
+        match problem() {
+            Ok((objectives, a_transform, upper_bounds)) => {
+                support_lp_batch(&self.solver, &objectives, &a_transform, &upper_bounds)
+                    .into_iter()
+                    .map(|result| result.map(|(value, point)| (value, DVector::from_vec(point))))
+                    .collect()
+            }
+            Err(e) => vec![Err(e); directions.ncols()],
+        }
     }
 }
 
 #[derive(Copy, Clone)]
-/// Statically allocated Halfspace polytope Hy \le h with a compact solution set
-pub struct SHalfspacePolytope<N, const R: usize, const C: usize> {
+/// Statically allocated Halfspace polyhedron Hy \le h, possibly unbounded or empty
+pub struct SHalfspacePolytope<N, const R: usize, const C: usize, S = MiniLp> {
     /// Hy \le h
     a_transform: SMatrix<N, R, C>,
     upper_bounds: SVector<N, R>,
+    solver: S,
 }
 
 impl<N, const R: usize, const C: usize> SHalfspacePolytope<N, R, C> {
@@ -83,49 +277,257 @@ impl<N, const R: usize, const C: usize> SHalfspacePolytope<N, R, C> {
         SHalfspacePolytope {
             a_transform,
             upper_bounds: h,
+            solver: MiniLp,
         }
     }
 }
 
-impl<N, const R: usize, const C: usize> LazySet<N, C> for SHalfspacePolytope<N, R, C>
+impl<N, const R: usize, const C: usize, S> SHalfspacePolytope<N, R, C, S> {
+    /// Use a different LP solver for the support function.
+    pub fn with_solver<T: LpSolver>(self, solver: T) -> SHalfspacePolytope<N, R, C, T> {
+        SHalfspacePolytope {
+            a_transform: self.a_transform,
+            upper_bounds: self.upper_bounds,
+            solver,
+        }
+    }
+}
+
+impl<N, const R: usize, const C: usize, S> SHalfspacePolytope<N, R, C, S>
+where
+    N: RealField + Copy + ToPrimitive,
+    S: LpSolver,
+{
+    /// The vertices of the polytope, following [`DHalfspacePolytope::vertices`].
+    pub fn vertices(&self) -> Result<Vec<SVector<N, C>>, SetError> {
+        let directions = box_directions::<N>(C);
+        let box_supports = self
+            .try_support_batch(&DirectionMatrix::from_column_slice(directions.as_slice()))
+            .into_iter()
+            .map(|support| support.map(|(value, _)| value));
+        let a_transform = DMatrix::from_fn(R, C, |i, j| self.a_transform[(i, j)]);
+        let upper_bounds = DVector::from_column_slice(self.upper_bounds.as_slice());
+        Ok(vertices(&a_transform, &upper_bounds, box_supports)?
+            .into_iter()
+            .map(|v| SVector::from_column_slice(v.as_slice()))
+            .collect())
+    }
+
+    /// Convert the polytope to its vertex representation, following [`Self::vertices`].
+    pub fn to_vpolytope(&self) -> Result<SVPolytope<N, C>, SetError> {
+        Ok(SVPolytope::new(self.vertices()?))
+    }
+
+    /// The volume of the polytope, following [`DHalfspacePolytope::volume`].
+    pub fn volume(&self) -> Result<N, SetError> {
+        self.to_vpolytope()?.volume()
+    }
+
+    /// Returns the polytope without redundant constraints, following
+    /// [`DHalfspacePolytope::remove_redundant_constraints`]. The number of remaining constraints
+    /// is only known at runtime, so the result is dynamically allocated.
+    pub fn remove_redundant_constraints(&self) -> Result<DHalfspacePolytope<N, S>, SetError>
+    where
+        S: Clone,
+    {
+        let (a_transform, upper_bounds) = irredundant_constraints(
+            &self.solver,
+            &DMatrix::from_fn(R, C, |i, j| self.a_transform[(i, j)]),
+            &DVector::from_column_slice(self.upper_bounds.as_slice()),
+        )?;
+        Ok(DHalfspacePolytope {
+            a_transform,
+            upper_bounds,
+            solver: self.solver.clone(),
+        })
+    }
+}
+
+impl<N, const R: usize, const C: usize, S> LazySet<N, C> for SHalfspacePolytope<N, R, C, S>
 where
     N: RealField + ToPrimitive,
+    S: LpSolver,
 {
     fn support(&self, direction: &SVector<N, C>) -> (N, SVector<N, C>) {
-        let mut problem = Problem::new(OptimizationDirection::Maximize);
-
-        // make a variable for every column in H
-        let mut vars = Vec::new();
-        for i in 0..self.a_transform.ncols() {
-            vars.push(problem.add_var(
-                direction[i].to_f64().unwrap(),
-                (f64::NEG_INFINITY, f64::INFINITY),
-            ));
-        }
+        expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &SVector<N, C>) -> Result<(N, SVector<N, C>), SetError> {
+        let (objective, point) = self.solver.maximize(
+            &DVector::from_vec(to_f64_vec(direction.iter())?),
+            &DMatrix::from_vec(R, C, to_f64_vec(self.a_transform.iter())?),
+            &DVector::from_vec(to_f64_vec(self.upper_bounds.iter())?),
+        )?;
+
+        let values = point
+            .iter()
+            .map(|x| from_f64(*x))
+            .collect::<Result<Vec<N>, _>>()?;
+        Ok((from_f64(objective)?, SVector::from_vec(values)))
+    }
 
-        // add constraints from H and h
-        for i in 0..self.a_transform.nrows() {
-            let mut row = Vec::<(Variable, f64)>::new();
-            for j in 0..self.a_transform.ncols() {
-                row.push((vars[j], self.a_transform[(i, j)].to_f64().unwrap()));
+    fn is_member(&self, point: &SVector<N, C>) -> bool {
+        (self.a_transform * point)
+            .iter()
+            .zip(self.upper_bounds.iter())
+            .all(|(ax, b)| within_bound(*ax, *b))
+    }
+
+    fn try_support_batch(
+        &self,
+        directions: &DirectionMatrix<N, C>,
+    ) -> Vec<Result<(N, SVector<N, C>), SetError>> {
+        // convert the constraints once for all directions
+        let problem = || -> Result<_, SetError> {
+            Ok((
+                DMatrix::from_vec(C, directions.ncols(), to_f64_vec(directions.iter())?),
+                DMatrix::from_vec(R, C, to_f64_vec(self.a_transform.iter())?),
+                DVector::from_vec(to_f64_vec(self.upper_bounds.iter())?),
+            ))
+        };
+        match problem() {
+            Ok((objectives, a_transform, upper_bounds)) => {
+                support_lp_batch(&self.solver, &objectives, &a_transform, &upper_bounds)
+                    .into_iter()
+                    .map(|result| result.map(|(value, point)| (value, SVector::from_vec(point))))
+                    .collect()
             }
-            // convert row into list
-            problem.add_constraint(
-                row.as_slice(),
-                ComparisonOp::Le,
-                self.upper_bounds[i].to_f64().unwrap(),
-            );
+            Err(e) => vec![Err(e); directions.ncols()],
         }
+    }
 
-        let solution = problem.solve().unwrap();
-        let objective = N::from_f64(solution.objective()).unwrap();
+    fn constraints(&self) -> Option<(DMatrix<N>, DVector<N>)> {
+        Some((
+            DMatrix::<N>::from_fn(R, C, |i, j| self.a_transform[(i, j)]),
+            DVector::<N>::from_column_slice(self.upper_bounds.as_slice()),
+        ))
+    }
+}
 
-        // collect variable values into vec
-        let mut values = Vec::<N>::new();
-        for i in 0..vars.len() {
-            values.push(N::from_f64(solution[vars[i]]).unwrap());
+/// Solves the support LPs for the columns of `objectives` in one solver batch, converting the
+/// results back to the scalar type. Solvers wrapped in [`crate::lp::WarmStart`] warm start the
+/// LPs from each other, see [`LpSolver::maximize_batch`].
+fn support_lp_batch<N, S>(
+    solver: &S,
+    objectives: &DMatrix<f64>,
+    a_transform: &DMatrix<f64>,
+    upper_bounds: &DVector<f64>,
+) -> Vec<Result<(N, Vec<N>), SetError>>
+where
+    N: RealField,
+    S: LpSolver,
+{
+    solver
+        .maximize_batch(objectives, a_transform, upper_bounds)
+        .into_iter()
+        .map(|result| {
+            let (objective, point) = result?;
+            let values = point
+                .iter()
+                .map(|x| from_f64(*x))
+                .collect::<Result<Vec<N>, _>>()?;
+            Ok((from_f64(objective)?, values))
+        })
+        .collect()
+}
+
+/// The constraints of Ax <= b without the ones that are implied by the others.
+fn irredundant_constraints<N, S>(
+    solver: &S,
+    a_transform: &DMatrix<N>,
+    upper_bounds: &DVector<N>,
+) -> Result<(DMatrix<N>, DVector<N>), SetError>
+where
+    N: RealField + Copy + ToPrimitive,
+    S: LpSolver,
+{
+    let rows: Vec<DVector<N>> = a_transform.row_iter().map(|row| row.transpose()).collect();
+    let mut kept = vec![true; rows.len()];
+    for i in 0..rows.len() {
+        let others = (0..rows.len()).filter(|j| *j != i && kept[*j]);
+        let faces: Vec<&[N]> = others.clone().map(|j| rows[j].as_slice()).collect();
+        let bounds: Vec<N> = others.map(|j| upper_bounds[j]).collect();
+        let redundant =
+            is_constraint_redundant(solver, &faces, &bounds, rows[i].as_slice(), upper_bounds[i]);
+        match redundant {
+            Ok(redundant) => kept[i] = !redundant,
+            // the other constraints already describe the empty set
+            Err(SetError::Infeasible) => {
+                return Ok((
+                    DMatrix::zeros(1, a_transform.ncols()),
+                    DVector::from_element(1, -N::one()),
+                ))
+            }
+            Err(e) => return Err(e),
         }
+    }
+
+    let indices: Vec<usize> = (0..rows.len()).filter(|i| kept[*i]).collect();
+    Ok((
+        a_transform.select_rows(indices.iter()),
+        upper_bounds.select_rows(indices.iter()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{DMatrix, DVector, SMatrix, SVector};
+
+    use super::{DHalfspacePolytope, SHalfspacePolytope};
+
+    /// The unit square followed by the given extra rows.
+    fn square_with(extra: &[([f64; 2], f64)]) -> DHalfspacePolytope<f64> {
+        let mut rows = vec![
+            ([1.0, 0.0], 1.0),
+            ([0.0, 1.0], 1.0),
+            ([-1.0, 0.0], 0.0),
+            ([0.0, -1.0], 0.0),
+        ];
+        rows.extend_from_slice(extra);
+        DHalfspacePolytope::new(
+            DMatrix::from_fn(rows.len(), 2, |i, j| rows[i].0[j]),
+            DVector::from_fn(rows.len(), |i, _| rows[i].1),
+        )
+    }
+
+    #[test]
+    fn redundant_row() {
+        let polytope = square_with(&[([1.0, 1.0], 5.0)]);
+        let reduced = polytope.remove_redundant_constraints().unwrap();
+        assert_eq!(reduced.a_transform, square_with(&[]).a_transform);
+        assert_eq!(reduced.upper_bounds, square_with(&[]).upper_bounds);
+    }
+
+    #[test]
+    fn duplicate_rows() {
+        let polytope = square_with(&[([1.0, 0.0], 1.0), ([2.0, 0.0], 2.0 + 1e-12)]);
+        let reduced = polytope.remove_redundant_constraints().unwrap();
+        assert_eq!(reduced.upper_bounds.len(), 4);
+        let rows = reduced.a_transform.row_iter();
+        assert_eq!(rows.filter(|row| row[0] > 0.0).count(), 1);
+    }
+
+    #[test]
+    fn empty_polytope() {
+        let polytope = square_with(&[([1.0, 1.0], -1.0)]);
+        let reduced = polytope.remove_redundant_constraints().unwrap();
+        assert_eq!(reduced.a_transform, DMatrix::zeros(1, 2));
+        assert_eq!(reduced.upper_bounds, DVector::from_element(1, -1.0));
+    }
 
-        (objective, SVector::from_vec(values))
+    #[test]
+    fn static_polytope() {
+        let polytope = SHalfspacePolytope::new(
+            SMatrix::<f64, 5, 2>::from_row_slice(&[
+                1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, 1.0, 1.0,
+            ]),
+            SVector::<f64, 5>::from_column_slice(&[1.0, 1.0, 0.0, 0.0, 5.0]),
+        );
+        let reduced = polytope.remove_redundant_constraints().unwrap();
+        assert_eq!(reduced.upper_bounds.len(), 4);
+        assert!(reduced
+            .a_transform
+            .row_iter()
+            .all(|row| row[0] + row[1] < 2.0));
     }
 }
@@ -0,0 +1,134 @@
+/*!
+ * Hyperplane
+ */
+use nalgebra::{DMatrix, DVector, RealField, SVector};
+
+use super::traits::{empty_support, unbounded_support};
+use super::{within_bound, LazySet, Universe};
+
+/// A hyperplane {x | a^T x = b}.
+#[derive(Clone)]
+pub struct Hyperplane<N, const D: usize> {
+    /// The normal vector a.
+    pub normal: SVector<N, D>,
+
+    /// The offset b.
+    pub offset: N,
+}
+
+impl<N, const D: usize> Hyperplane<N, D>
+where
+    N: RealField,
+{
+    /// Create a new hyperplane.
+    pub fn new(normal: SVector<N, D>, offset: N) -> Hyperplane<N, D> {
+        Hyperplane { normal, offset }
+    }
+}
+
+impl<N, const D: usize> LazySet<N, D> for Hyperplane<N, D>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        // 0^T x = b holds everywhere or nowhere
+        if self.normal.iter().all(|a| a.is_zero()) {
+            if self.offset.is_zero() {
+                return Universe.support(direction);
+            }
+            return empty_support();
+        }
+        // bounded only if the direction is a multiple of the normal
+        match normal_multiple(&self.normal, direction) {
+            Some(lambda) => (
+                lambda * self.offset,
+                self.normal * (self.offset / self.normal.norm_squared()),
+            ),
+            None => unbounded_support(direction),
+        }
+    }
+
+    fn constraints(&self) -> Option<(DMatrix<N>, DVector<N>)> {
+        // a^T x <= b and -a^T x <= -b
+        let a_transform = DMatrix::<N>::from_fn(2, D, |i, j| {
+            if i == 0 {
+                self.normal[j]
+            } else {
+                -self.normal[j]
+            }
+        });
+        Some((
+            a_transform,
+            DVector::from_vec(vec![self.offset, -self.offset]),
+        ))
+    }
+
+    fn is_member(&self, point: &SVector<N, D>) -> bool {
+        // a^T x <= b and -a^T x <= -b, as in the constraints
+        let value = self.normal.dot(point);
+        within_bound(value, self.offset) && within_bound(-value, -self.offset)
+    }
+}
+
+/// Returns lambda if the direction equals lambda * normal, up to a relative tolerance of sqrt(eps)
+/// as in [`within_bound`], so that directions mapped through a transformation still count.
+pub(crate) fn normal_multiple<N, const D: usize>(
+    normal: &SVector<N, D>,
+    direction: &SVector<N, D>,
+) -> Option<N>
+where
+    N: RealField,
+{
+    let lambda = direction.dot(normal) / normal.norm_squared();
+    let residual = direction - normal * lambda;
+    if residual.norm() <= N::default_epsilon().sqrt() * direction.norm() {
+        Some(lambda)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use nalgebra::{Rotation2, Vector2};
+
+    use crate::convex::{HalfSpace, Hyperplane, LazySet};
+    use crate::error::SetError;
+    use crate::lazy_operation::LinearTransformation;
+
+    #[test]
+    fn rotated_halfspace_along_its_normal() {
+        let normal = Vector2::new(1.0, 2.0);
+        for k in 0..100 {
+            let rotation = Rotation2::new(0.0628 * k as f64).into_inner();
+            let halfspace = HalfSpace::new(normal, 1.0);
+            let rotated = LinearTransformation::new(rotation, Arc::new(halfspace));
+            let (value, _) = rotated.try_support(&(rotation * normal)).unwrap();
+            assert!((value - 1.0).abs() < 1e-9, "{}: {}", k, value);
+        }
+    }
+
+    #[test]
+    fn zero_normal() {
+        let direction = Vector2::new(1.0, 0.0);
+        let empty = HalfSpace::new(Vector2::zeros(), -1.0f64);
+        assert!(empty.is_empty());
+        assert_eq!(
+            empty.try_support(&direction).err(),
+            Some(SetError::Infeasible)
+        );
+        let everything = HalfSpace::new(Vector2::zeros(), 0.0f64);
+        assert!(!everything.is_empty());
+        assert_eq!(
+            everything.try_support(&direction).err(),
+            Some(SetError::Unbounded)
+        );
+
+        assert!(Hyperplane::new(Vector2::zeros(), 1.0f64).is_empty());
+        let everything = Hyperplane::new(Vector2::zeros(), 0.0f64);
+        assert!(!everything.is_empty());
+        assert_eq!(everything.try_support(&Vector2::zeros()).unwrap().0, 0.0);
+    }
+}
@@ -0,0 +1,111 @@
+/*!
+ * Hyperrectangle
+ */
+use nalgebra::{DMatrix, DVector, RealField, SVector};
+
+use super::traits::{empty_support, within_bound};
+use super::LazySet;
+
+/// An axis-aligned hyperrectangle (box) given by its center and a vector of radii.
+#[derive(Clone)]
+pub struct Hyperrectangle<N, const D: usize> {
+    /// The center of the box.
+    pub center: SVector<N, D>,
+
+    /// The half-width of the box along each axis.
+    pub radius: SVector<N, D>,
+}
+
+impl<N, const D: usize> Hyperrectangle<N, D>
+where
+    N: RealField,
+{
+    /// Create a new hyperrectangle.
+    pub fn new(center: SVector<N, D>, radius: SVector<N, D>) -> Hyperrectangle<N, D> {
+        Hyperrectangle { center, radius }
+    }
+
+    /// Create a new hyperrectangle from its lower and upper corners.
+    pub fn from_bounds(low: SVector<N, D>, high: SVector<N, D>) -> Hyperrectangle<N, D> {
+        let two = N::one() + N::one();
+        Hyperrectangle {
+            center: (high + low) / two,
+            radius: (high - low) / two,
+        }
+    }
+
+    /// The lower corner of the box.
+    pub fn low(&self) -> SVector<N, D> {
+        self.center - self.radius
+    }
+
+    /// The upper corner of the box.
+    pub fn high(&self) -> SVector<N, D> {
+        self.center + self.radius
+    }
+
+    /// The volume of the box, zero if it is empty.
+    pub fn volume(&self) -> N
+    where
+        N: Copy,
+    {
+        let two = N::one() + N::one();
+        self.radius
+            .iter()
+            .fold(N::one(), |volume, r| volume * (two * *r).max(N::zero()))
+    }
+}
+
+impl<N, const D: usize> LazySet<N, D> for Hyperrectangle<N, D>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        if self.is_empty() {
+            return empty_support();
+        }
+        // <d, c> + sum |d_i| r_i, attained at the vertex c + sign(d) r
+        let mut point = self.center;
+        for i in 0..D {
+            if direction[i] >= N::zero() {
+                point[i] += self.radius[i];
+            } else {
+                point[i] -= self.radius[i];
+            }
+        }
+        (direction.dot(&point), point)
+    }
+
+    fn constraints(&self) -> Option<(DMatrix<N>, DVector<N>)> {
+        Some(box_constraints(&self.low(), &self.high()))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.radius.iter().any(|r| *r < N::zero())
+    }
+
+    fn is_member(&self, point: &SVector<N, D>) -> bool {
+        (0..D).all(|i| within_bound((point[i] - self.center[i]).abs(), self.radius[i]))
+    }
+}
+
+/// The constraints `x <= high` and `-x <= -low` of an axis-aligned box.
+pub(crate) fn box_constraints<N, const D: usize>(
+    low: &SVector<N, D>,
+    high: &SVector<N, D>,
+) -> (DMatrix<N>, DVector<N>)
+where
+    N: RealField,
+{
+    let a_transform = DMatrix::<N>::from_fn(2 * D, D, |i, j| {
+        if i == j {
+            N::one()
+        } else if i == j + D {
+            -N::one()
+        } else {
+            N::zero()
+        }
+    });
+    let b = DVector::<N>::from_fn(2 * D, |i, _| if i < D { high[i] } else { -low[i - D] });
+    (a_transform, b)
+}
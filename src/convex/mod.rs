@@ -1,12 +1,29 @@
 /*!
 * Convex Sets
 */
+mod conversion;
+pub mod ellipsoid;
+pub mod empty;
 pub mod halfspace;
+pub mod hyperplane;
+pub mod hyperrectangle;
 pub mod singleton;
 pub mod sphere;
 mod traits;
+pub mod universe;
+pub mod vpolytope;
+pub mod zonotope;
 
-pub use halfspace::{DHalfspacePolytope, SHalfspacePolytope};
+pub(crate) use conversion::{affine_hull, box_directions, facets, planar_hull};
+pub use ellipsoid::Ellipsoid;
+pub use empty::EmptySet;
+pub use halfspace::{DHalfspacePolytope, HalfSpace, SHalfspacePolytope};
+pub use hyperplane::Hyperplane;
+pub use hyperrectangle::Hyperrectangle;
 pub use singleton::Singleton;
 pub use sphere::Hypersphere;
-pub use traits::{DLazySet, LazySet};
+pub(crate) use traits::{d_expect_support, expect_support, within_bound};
+pub use traits::{DLazySet, DSharedSet, DirectionMatrix, LazySet, SharedSet};
+pub use universe::Universe;
+pub use vpolytope::{DVPolytope, SVPolytope};
+pub use zonotope::{DZonotope, Zonotope};
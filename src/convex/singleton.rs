@@ -1,9 +1,10 @@
 /*!
  * A singleton.
  */
-use nalgebra::{RealField, SVector};
+use nalgebra::{DMatrix, DVector, RealField, SVector};
 
-use super::LazySet;
+use super::hyperrectangle::box_constraints;
+use super::{within_bound, LazySet};
 
 /// A singleton.
 #[derive(Clone)]
@@ -29,4 +30,19 @@ where
     fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
         (direction.dot(&self.point), self.point)
     }
+
+    fn constraints(&self) -> Option<(DMatrix<N>, DVector<N>)> {
+        Some(box_constraints(&self.point, &self.point))
+    }
+
+    fn is_empty(&self) -> bool {
+        false
+    }
+
+    fn is_member(&self, point: &SVector<N, D>) -> bool {
+        point
+            .iter()
+            .zip(self.point.iter())
+            .all(|(x, p)| within_bound(*x, *p) && within_bound(-*x, -*p))
+    }
 }
@@ -2,7 +2,8 @@
  * HyperSphere
  */
 
-use super::traits::LazySet;
+use super::traits::{empty_support, within_bound, LazySet};
+use crate::volume::ball_volume;
 use nalgebra::{RealField, SVector};
 
 /// A hyper-sphere.
@@ -23,6 +24,14 @@ where
     pub fn new(radius: N, center: SVector<N, D>) -> Hypersphere<N, D> {
         Hypersphere { radius, center }
     }
+
+    /// The volume of the sphere, pi^(d/2) / Gamma(d/2 + 1) r^d.
+    pub fn volume(&self) -> N
+    where
+        N: Copy,
+    {
+        ball_volume(D, self.radius)
+    }
 }
 
 impl<N, const D: usize> LazySet<N, D> for Hypersphere<N, D>
@@ -30,10 +39,26 @@ where
     N: RealField,
 {
     fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
-        let d = direction.normalize();
-        let y = d.scale(self.radius);
+        if self.is_empty() {
+            return empty_support();
+        }
+        // <d, c> + r ||d||, attained at c + r d / ||d||
+        let norm = direction.norm();
+        let o = direction.dot(&self.center);
+        if norm.is_zero() {
+            return (o, self.center);
+        }
+        (
+            self.radius * norm + o,
+            self.center + direction.scale(self.radius / norm),
+        )
+    }
+
+    fn is_empty(&self) -> bool {
+        self.radius < N::zero()
+    }
 
-        let (o, d) = (direction.dot(&self.center), self.center);
-        (self.radius * d.norm() + o, y + d)
+    fn is_member(&self, point: &SVector<N, D>) -> bool {
+        within_bound((point - self.center).norm(), self.radius)
     }
 }
@@ -1,15 +1,285 @@
-use nalgebra::{DVector, SVector};
+use std::sync::Arc;
+
+use nalgebra::{Const, DMatrix, DVector, Dynamic, OMatrix, RealField, SVector};
+
+use crate::check::separating_direction;
+use crate::error::SetError;
+use crate::lazy_operation::Cached;
 
 /// A support function is a function that returns the support point of a convex set in a given direction.
+///
+/// Sets that are unbounded in a direction report a support value of `+inf` together with a support
+/// vector whose entries are infinite along the direction. The empty set reports `-inf` and a support
+/// vector of NaNs.
 pub trait LazySet<N, const D: usize> {
     /// Returns the support function and support point of the convex set in the given direction.
     /// They are done together for performance and simplicity.
     fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>);
+
+    /// Returns the support function and support point, reporting unbounded and empty sets and
+    /// numerical failures as errors instead of infinite values or panics.
+    fn try_support(&self, direction: &SVector<N, D>) -> Result<(N, SVector<N, D>), SetError>
+    where
+        N: RealField,
+    {
+        classify_support(self.support(direction))
+    }
+
+    /// Returns the support functions and support points in the directions given by the columns of
+    /// the matrix.
+    fn support_batch(&self, directions: &DirectionMatrix<N, D>) -> Vec<(N, SVector<N, D>)>
+    where
+        N: RealField,
+    {
+        self.try_support_batch(directions)
+            .into_iter()
+            .zip(directions.column_iter())
+            .map(|(result, direction)| expect_support(result, &direction.into_owned()))
+            .collect()
+    }
+
+    /// Returns [`LazySet::try_support`] for each column of the matrix. Sets override this when
+    /// the directions can share work.
+    fn try_support_batch(
+        &self,
+        directions: &DirectionMatrix<N, D>,
+    ) -> Vec<Result<(N, SVector<N, D>), SetError>>
+    where
+        N: RealField,
+    {
+        directions
+            .column_iter()
+            .map(|direction| self.try_support(&direction.into_owned()))
+            .collect()
+    }
+
+    /// Returns the constraints `(A, b)` of an H-representation `Ax <= b` of the set, or `None` if
+    /// the set is not known to be polyhedral.
+    fn constraints(&self) -> Option<(DMatrix<N>, DVector<N>)> {
+        None
+    }
+
+    /// Returns true if the set is empty.
+    ///
+    /// The default evaluates the support function in the zero direction, which is an LP
+    /// feasibility check for the halfspace polytopes.
+    fn is_empty(&self) -> bool
+    where
+        N: RealField,
+    {
+        matches!(
+            self.try_support(&SVector::zeros()),
+            Err(SetError::Infeasible)
+        )
+    }
+
+    /// Returns true if the point lies in the set.
+    ///
+    /// Sets override this with exact tests. The default searches for a direction separating the
+    /// point from the set with support queries, so `false` is always correct while points within
+    /// a small tolerance of the set, or for which the search is inconclusive, are reported as
+    /// members.
+    fn is_member(&self, point: &SVector<N, D>) -> bool
+    where
+        N: RealField,
+    {
+        let support = |d: &DVector<N>| {
+            self.try_support(&SVector::from_column_slice(d.as_slice()))
+                .map(|(rho, p)| (rho, DVector::from_column_slice(p.as_slice())))
+        };
+        !matches!(
+            separating_direction(support, &DVector::from_column_slice(point.as_slice())),
+            Ok(Some(_)) | Err(SetError::Infeasible)
+        )
+    }
+
+    /// Wraps the set in a [`Cached`] that remembers its support evaluations per direction.
+    fn cached(self) -> Cached<Self, N, D>
+    where
+        Self: Sized,
+    {
+        Cached::new(self)
+    }
 }
 
+/// A matrix whose columns are directions in dimension D.
+pub type DirectionMatrix<N, const D: usize> = OMatrix<N, Const<D>, Dynamic>;
+
+/// A shared, thread safe handle to a set, so that one set can be an operand of several operations.
+pub type SharedSet<N, const D: usize> = Arc<dyn LazySet<N, D> + Send + Sync>;
+
 /// A support function is a function that returns the support point of a convex set in a given direction.
+///
+/// Follows the same conventions as [`LazySet`] for unbounded and empty sets.
 pub trait DLazySet<N> {
     /// Returns the support function and support point of the convex set in the given direction.
     /// They are done together for performance and simplicity.
     fn support(&self, direction: &DVector<N>) -> (N, DVector<N>);
+
+    /// Returns the support function and support point, reporting unbounded and empty sets and
+    /// numerical failures as errors instead of infinite values or panics.
+    fn try_support(&self, direction: &DVector<N>) -> Result<(N, DVector<N>), SetError>
+    where
+        N: RealField,
+    {
+        classify_support(self.support(direction))
+    }
+
+    /// Returns true if the set is empty.
+    ///
+    /// The dimension of a dynamically sized set isn't known here, so the default reports the set
+    /// as non-empty. Sets that can be empty override this.
+    fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Returns true if the point lies in the set, following the conventions of
+    /// [`LazySet::is_member`].
+    fn is_member(&self, point: &DVector<N>) -> bool
+    where
+        N: RealField,
+    {
+        !matches!(
+            separating_direction(|d| self.try_support(d), point),
+            Ok(Some(_)) | Err(SetError::Infeasible | SetError::DimensionMismatch { .. })
+        )
+    }
+
+    /// Returns the support functions and support points in the directions given by the columns of
+    /// the matrix.
+    fn support_batch(&self, directions: &DMatrix<N>) -> Vec<(N, DVector<N>)>
+    where
+        N: RealField,
+    {
+        self.try_support_batch(directions)
+            .into_iter()
+            .zip(directions.column_iter())
+            .map(|(result, direction)| d_expect_support(result, &direction.into_owned()))
+            .collect()
+    }
+
+    /// Returns [`DLazySet::try_support`] for each column of the matrix. Sets override this when
+    /// the directions can share work.
+    fn try_support_batch(&self, directions: &DMatrix<N>) -> Vec<Result<(N, DVector<N>), SetError>>
+    where
+        N: RealField,
+    {
+        directions
+            .column_iter()
+            .map(|direction| self.try_support(&direction.into_owned()))
+            .collect()
+    }
+}
+
+/// A shared, thread safe handle to a dynamically sized set.
+pub type DSharedSet<N> = Arc<dyn DLazySet<N> + Send + Sync>;
+
+/// Positive infinity in the scalar type.
+pub(crate) fn infinity<N: RealField>() -> N {
+    nalgebra::convert(f64::INFINITY)
+}
+
+/// The support of a set that is unbounded in the given direction.
+pub(crate) fn unbounded_support<N, const D: usize>(direction: &SVector<N, D>) -> (N, SVector<N, D>)
+where
+    N: RealField,
+{
+    (infinity(), direction.map(unbounded_coordinate))
+}
+
+/// The support of a dynamically allocated set that is unbounded in the given direction.
+pub(crate) fn d_unbounded_support<N>(direction: &DVector<N>) -> (N, DVector<N>)
+where
+    N: RealField,
+{
+    (infinity(), direction.map(unbounded_coordinate))
+}
+
+/// The support of the empty set.
+pub(crate) fn empty_support<N, const D: usize>() -> (N, SVector<N, D>)
+where
+    N: RealField,
+{
+    let nan: N = nalgebra::convert(f64::NAN);
+    (-infinity::<N>(), SVector::from_element(nan))
+}
+
+/// The support of the empty set in a space of the given dimension.
+pub(crate) fn d_empty_support<N>(dim: usize) -> (N, DVector<N>)
+where
+    N: RealField,
+{
+    let nan: N = nalgebra::convert(f64::NAN);
+    (-infinity::<N>(), DVector::from_element(dim, nan))
+}
+
+/// Whether a support value reports the empty set.
+pub(crate) fn is_empty_value<N: RealField>(value: N) -> bool {
+    value == -infinity::<N>()
+}
+
+/// Whether a value is at most a bound, up to the relative tolerance of the membership checks.
+pub(crate) fn within_bound<N: RealField>(value: N, bound: N) -> bool {
+    let tol = N::default_epsilon().sqrt() * (N::one() + bound.abs());
+    value <= bound + tol
+}
+
+/// Maps infinite support values to the corresponding errors.
+fn classify_support<N, V>((value, point): (N, V)) -> Result<(N, V), SetError>
+where
+    N: RealField,
+{
+    if is_empty_value(value) {
+        Err(SetError::Infeasible)
+    } else if value == infinity() {
+        Err(SetError::Unbounded)
+    } else {
+        Ok((value, point))
+    }
+}
+
+/// Maps the result of a fallible support query back to infinite support values.
+///
+/// Panics on errors that have no infinite-value counterpart.
+pub(crate) fn expect_support<N, const D: usize>(
+    result: Result<(N, SVector<N, D>), SetError>,
+    direction: &SVector<N, D>,
+) -> (N, SVector<N, D>)
+where
+    N: RealField,
+{
+    match result {
+        Ok(support) => support,
+        Err(SetError::Unbounded) => unbounded_support(direction),
+        Err(SetError::Infeasible) => empty_support(),
+        Err(e) => panic!("support evaluation failed: {}", e),
+    }
+}
+
+/// Maps the result of a fallible dynamic support query back to infinite support values.
+///
+/// Panics on errors that have no infinite-value counterpart.
+pub(crate) fn d_expect_support<N>(
+    result: Result<(N, DVector<N>), SetError>,
+    direction: &DVector<N>,
+) -> (N, DVector<N>)
+where
+    N: RealField,
+{
+    match result {
+        Ok(support) => support,
+        Err(SetError::Unbounded) => d_unbounded_support(direction),
+        Err(SetError::Infeasible) => d_empty_support(direction.len()),
+        Err(e) => panic!("support evaluation failed: {}", e),
+    }
+}
+
+fn unbounded_coordinate<N: RealField>(d: N) -> N {
+    if d > N::zero() {
+        infinity()
+    } else if d < N::zero() {
+        -infinity::<N>()
+    } else {
+        N::zero()
+    }
 }
@@ -0,0 +1,42 @@
+/*!
+ * The universe, the set of all points.
+ */
+use nalgebra::{DMatrix, DVector, RealField, SVector};
+
+use super::traits::unbounded_support;
+use super::LazySet;
+
+/// The universal set containing every point of the space.
+#[derive(Copy, Clone, Default)]
+pub struct Universe;
+
+impl Universe {
+    /// Create a new universe.
+    pub fn new() -> Universe {
+        Universe
+    }
+}
+
+impl<N, const D: usize> LazySet<N, D> for Universe
+where
+    N: RealField,
+{
+    fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        if direction.iter().all(|d| d.is_zero()) {
+            return (N::zero(), SVector::zeros());
+        }
+        unbounded_support(direction)
+    }
+
+    fn constraints(&self) -> Option<(DMatrix<N>, DVector<N>)> {
+        Some((DMatrix::zeros(0, D), DVector::zeros(0)))
+    }
+
+    fn is_empty(&self) -> bool {
+        false
+    }
+
+    fn is_member(&self, _point: &SVector<N, D>) -> bool {
+        true
+    }
+}
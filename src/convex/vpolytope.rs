@@ -0,0 +1,265 @@
+/*!
+ * Vertex Represented Polytopes
+ */
+use nalgebra::{DMatrix, DVector, RealField, SVector};
+use num_traits::ToPrimitive;
+
+use super::conversion::facets;
+use super::traits::{d_expect_support, empty_support};
+use super::{DHalfspacePolytope, DLazySet, LazySet};
+use crate::error::SetError;
+use crate::volume::hull_volume;
+
+#[derive(Clone)]
+/// Statically allocated polytope given as the convex hull of a list of vertices
+pub struct SVPolytope<N, const D: usize> {
+    /// The vertices of the polytope.
+    pub vertices: Vec<SVector<N, D>>,
+}
+
+impl<N, const D: usize> SVPolytope<N, D> {
+    /// Create a new vertex polytope.
+    pub fn new(vertices: Vec<SVector<N, D>>) -> SVPolytope<N, D> {
+        SVPolytope { vertices }
+    }
+}
+
+impl<N, const D: usize> SVPolytope<N, D>
+where
+    N: RealField + Copy + ToPrimitive,
+{
+    /// Convert the polytope to its halfspace representation, following
+    /// [`DVPolytope::to_halfspace`]. The polytope without vertices becomes 0^T x <= -1.
+    pub fn to_halfspace(&self) -> Result<DHalfspacePolytope<N>, SetError> {
+        if self.vertices.is_empty() {
+            return Ok(DHalfspacePolytope::new(
+                DMatrix::zeros(1, D),
+                DVector::from_element(1, -N::one()),
+            ));
+        }
+        let points: Vec<DVector<N>> = self
+            .vertices
+            .iter()
+            .map(|v| DVector::from_column_slice(v.as_slice()))
+            .collect();
+        let (a_transform, b) = facets(&points)?;
+        Ok(DHalfspacePolytope::new(a_transform, b))
+    }
+
+    /// The volume of the polytope, following [`DVPolytope::volume`].
+    pub fn volume(&self) -> Result<N, SetError> {
+        let points: Vec<DVector<N>> = self
+            .vertices
+            .iter()
+            .map(|v| DVector::from_column_slice(v.as_slice()))
+            .collect();
+        hull_volume(&points)
+    }
+}
+
+impl<N, const D: usize> LazySet<N, D> for SVPolytope<N, D>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        // the maximum of a linear function over a polytope is attained at a vertex
+        let mut vertices = self.vertices.iter();
+        let first = match vertices.next() {
+            Some(first) => first,
+            None => return empty_support(),
+        };
+        let mut best = (direction.dot(first), *first);
+        for v in vertices {
+            let value = direction.dot(v);
+            if value > best.0 {
+                best = (value, *v);
+            }
+        }
+        best
+    }
+
+    fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+}
+
+#[derive(Clone)]
+/// Dynamically allocated polytope given as the convex hull of a list of vertices
+pub struct DVPolytope<N> {
+    /// The vertices of the polytope.
+    pub vertices: Vec<DVector<N>>,
+}
+
+impl<N> DVPolytope<N> {
+    /// Create a new vertex polytope.
+    pub fn new(vertices: Vec<DVector<N>>) -> DVPolytope<N> {
+        DVPolytope { vertices }
+    }
+}
+
+impl<N> DVPolytope<N>
+where
+    N: RealField + Copy + ToPrimitive,
+{
+    /// Convert the polytope to its halfspace representation by facet enumeration.
+    ///
+    /// The facets are the vertices of the polar polytope, and in two dimensions the edges of the
+    /// convex hull in counter-clockwise order. Polytopes that are not full-dimensional are
+    /// described by their facets within their affine hull and pairs of opposite constraints
+    /// orthogonal to it. Reports [`SetError::Infeasible`] for the polytope without vertices,
+    /// whose dimension is unknown.
+    pub fn to_halfspace(&self) -> Result<DHalfspacePolytope<N>, SetError> {
+        let (a_transform, b) = facets(&self.vertices)?;
+        Ok(DHalfspacePolytope::new(a_transform, b))
+    }
+
+    /// The exact volume of the polytope, computed from the vertices as described in
+    /// [`crate::volume`].
+    ///
+    /// Polytopes that are not full-dimensional and the polytope without vertices have volume zero.
+    pub fn volume(&self) -> Result<N, SetError> {
+        hull_volume(&self.vertices)
+    }
+}
+
+impl<N> DLazySet<N> for DVPolytope<N>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &DVector<N>) -> (N, DVector<N>) {
+        d_expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &DVector<N>) -> Result<(N, DVector<N>), SetError> {
+        let mut best: Option<(N, &DVector<N>)> = None;
+        for v in self.vertices.iter() {
+            if v.len() != direction.len() {
+                return Err(SetError::DimensionMismatch {
+                    expected: v.len(),
+                    found: direction.len(),
+                });
+            }
+            let value = direction.dot(v);
+            if best.map_or(true, |(b, _)| value > b) {
+                best = Some((value, v));
+            }
+        }
+        match best {
+            Some((value, v)) => Ok((value, v.clone())),
+            None => Err(SetError::Infeasible),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{DVector, Rotation3, Vector3};
+
+    use super::DVPolytope;
+    use crate::convex::DLazySet;
+
+    /// The vertices of the box [-1, 1]^d.
+    fn box_vertices(dimension: usize) -> Vec<DVector<f64>> {
+        (0..1usize << dimension)
+            .map(|corner| {
+                DVector::from_fn(
+                    dimension,
+                    |i, _| {
+                        if corner & (1 << i) != 0 {
+                            1.0
+                        } else {
+                            -1.0
+                        }
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// The vertices ±e_i of the cross-polytope in dimension d.
+    fn cross_vertices(dimension: usize) -> Vec<DVector<f64>> {
+        (0..2 * dimension)
+            .map(|k| {
+                let sign = if k < dimension { 1.0 } else { -1.0 };
+                DVector::from_fn(
+                    dimension,
+                    |i, _| if i == k % dimension { sign } else { 0.0 },
+                )
+            })
+            .collect()
+    }
+
+    /// Converts the polytope to its H-representation, checks that both agree in the box
+    /// directions and along (1, 2, ..., d) and returns the number of constraints.
+    fn round_trip(vertices: Vec<DVector<f64>>) -> usize {
+        let dimension = vertices[0].len();
+        let polytope = DVPolytope::new(vertices);
+        let halfspaces = polytope.to_halfspace().unwrap();
+        let mut directions: Vec<DVector<f64>> = (0..2 * dimension)
+            .map(|k| {
+                let sign = if k < dimension { 1.0 } else { -1.0 };
+                DVector::from_fn(
+                    dimension,
+                    |i, _| if i == k % dimension { sign } else { 0.0 },
+                )
+            })
+            .collect();
+        directions.push(DVector::from_fn(dimension, |i, _| (i + 1) as f64));
+        for d in directions {
+            let (value, _) = halfspaces.try_support(&d).unwrap();
+            let (expected, _) = polytope.try_support(&d).unwrap();
+            assert!(
+                (value - expected).abs() <= 1e-6,
+                "{}: {} {}",
+                d,
+                value,
+                expected
+            );
+        }
+        for v in &polytope.vertices {
+            assert!(halfspaces.is_member(v), "{}", v);
+        }
+        halfspaces.upper_bounds.len()
+    }
+
+    #[test]
+    fn cube_round_trip() {
+        assert_eq!(round_trip(box_vertices(3)), 6);
+    }
+
+    #[test]
+    fn octahedron_round_trip() {
+        assert_eq!(round_trip(cross_vertices(3)), 8);
+    }
+
+    #[test]
+    fn box_4d_round_trip() {
+        let shifted = box_vertices(4)
+            .into_iter()
+            .map(|v| v.add_scalar(0.3))
+            .collect();
+        assert_eq!(round_trip(shifted), 8);
+    }
+
+    #[test]
+    fn flat_quadrilateral_round_trip() {
+        let corners = [[0.0, 0.0], [2.0, 0.0], [1.5, 1.0], [0.2, 0.8]];
+        for k in 0..50 {
+            let angle = k as f64 * 0.37;
+            let rotation = Rotation3::from_euler_angles(angle, 2.0 * angle + 0.1, 3.0 * angle);
+            let vertices = corners
+                .iter()
+                .map(|[x, y]| {
+                    let v = rotation * Vector3::new(*x, *y, 0.5);
+                    DVector::from_column_slice(v.as_slice())
+                })
+                .collect();
+            // four edges within the plane and two opposite constraints orthogonal to it
+            assert_eq!(round_trip(vertices), 6, "{}", k);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+/*!
+ * Zonotopes
+ */
+use nalgebra::{Const, DMatrix, DVector, Dynamic, OMatrix, RealField, SMatrix, SVector, Scalar};
+
+use super::traits::d_expect_support;
+use super::{DLazySet, LazySet};
+use crate::error::SetError;
+use crate::volume::zonotope_volume;
+
+/// Statically allocated zonotope c + G[-1, 1]^p with a variable number of generators.
+#[derive(Clone)]
+pub struct Zonotope<N: Scalar, const D: usize> {
+    /// The center of the zonotope.
+    pub center: SVector<N, D>,
+    /// The generators, one per column.
+    pub generators: OMatrix<N, Const<D>, Dynamic>,
+}
+
+impl<N, const D: usize> Zonotope<N, D>
+where
+    N: RealField,
+{
+    /// Create a new zonotope.
+    pub fn new(center: SVector<N, D>, generators: OMatrix<N, Const<D>, Dynamic>) -> Zonotope<N, D> {
+        Zonotope { center, generators }
+    }
+
+    /// The concrete image of the zonotope under a linear map.
+    pub fn linear_map(&self, a_transform: &SMatrix<N, D, D>) -> Zonotope<N, D> {
+        Zonotope {
+            center: a_transform * self.center,
+            generators: a_transform * &self.generators,
+        }
+    }
+
+    /// The concrete Minkowski sum of two zonotopes, concatenating their generators.
+    pub fn minkowski_sum(&self, other: &Zonotope<N, D>) -> Zonotope<N, D> {
+        let (n1, n2) = (self.generators.ncols(), other.generators.ncols());
+        let mut generators = OMatrix::<N, Const<D>, Dynamic>::zeros(n1 + n2);
+        generators.columns_mut(0, n1).copy_from(&self.generators);
+        generators.columns_mut(n1, n2).copy_from(&other.generators);
+        Zonotope {
+            center: self.center + other.center,
+            generators,
+        }
+    }
+
+    /// The volume of the zonotope, following [`DZonotope::volume`].
+    pub fn volume(&self) -> N
+    where
+        N: Copy,
+    {
+        zonotope_volume(&DMatrix::from_fn(D, self.generators.ncols(), |i, j| {
+            self.generators[(i, j)]
+        }))
+    }
+}
+
+impl<N, const D: usize> LazySet<N, D> for Zonotope<N, D>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        // <d, c> + sum |<d, g_i>|, attained at c + sum sign(<d, g_i>) g_i
+        let mut point = self.center;
+        for g in self.generators.column_iter() {
+            if g.dot(direction) >= N::zero() {
+                point += g;
+            } else {
+                point -= g;
+            }
+        }
+        (direction.dot(&point), point)
+    }
+
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+/// Dynamically allocated zonotope c + G[-1, 1]^p.
+#[derive(Clone)]
+pub struct DZonotope<N> {
+    /// The center of the zonotope.
+    pub center: DVector<N>,
+    /// The generators, one per column.
+    pub generators: DMatrix<N>,
+}
+
+impl<N> DZonotope<N>
+where
+    N: RealField,
+{
+    /// Create a new zonotope.
+    pub fn new(center: DVector<N>, generators: DMatrix<N>) -> DZonotope<N> {
+        DZonotope { center, generators }
+    }
+
+    /// The concrete image of the zonotope under a linear map.
+    pub fn linear_map(&self, a_transform: &DMatrix<N>) -> DZonotope<N> {
+        DZonotope {
+            center: a_transform * &self.center,
+            generators: a_transform * &self.generators,
+        }
+    }
+
+    /// The concrete Minkowski sum of two zonotopes, concatenating their generators.
+    pub fn minkowski_sum(&self, other: &DZonotope<N>) -> DZonotope<N> {
+        let (n1, n2) = (self.generators.ncols(), other.generators.ncols());
+        let mut generators = DMatrix::<N>::zeros(self.center.len(), n1 + n2);
+        generators.columns_mut(0, n1).copy_from(&self.generators);
+        generators.columns_mut(n1, n2).copy_from(&other.generators);
+        DZonotope {
+            center: &self.center + &other.center,
+            generators,
+        }
+    }
+
+    /// The volume of the zonotope, 2^d times the sum of |det| over all choices of d generators.
+    ///
+    /// The number of choices grows quickly with the number of generators.
+    pub fn volume(&self) -> N
+    where
+        N: Copy,
+    {
+        zonotope_volume(&self.generators)
+    }
+}
+
+impl<N> DLazySet<N> for DZonotope<N>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &DVector<N>) -> (N, DVector<N>) {
+        d_expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &DVector<N>) -> Result<(N, DVector<N>), SetError> {
+        if direction.len() != self.center.len() {
+            return Err(SetError::DimensionMismatch {
+                expected: self.center.len(),
+                found: direction.len(),
+            });
+        }
+        let mut point = self.center.clone();
+        for g in self.generators.column_iter() {
+            if g.dot(direction) >= N::zero() {
+                point += g;
+            } else {
+                point -= g;
+            }
+        }
+        Ok((direction.dot(&point), point))
+    }
+}
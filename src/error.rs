@@ -0,0 +1,56 @@
+/*!
+ * Errors raised while evaluating sets.
+ */
+use std::fmt;
+
+/// An error raised while evaluating a lazy set.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SetError {
+    /// The set is empty, e.g. its constraints can't simultaneously be satisfied.
+    Infeasible,
+    /// The set is unbounded in the queried direction.
+    Unbounded,
+    /// The linear programming solver failed internally.
+    SolverFailure,
+    /// A value could not be converted to or from the floating point type used by a solver.
+    NumericConversion,
+    /// A tolerance is not a positive number.
+    InvalidTolerance,
+    /// The dimension of a Krylov subspace is zero.
+    InvalidKrylovDimension,
+    /// An iterative algorithm did not reach the requested accuracy within its iteration limit.
+    NotConverged,
+    /// The set is not known to be polyhedral, i.e. it has no constraints.
+    NotPolyhedral,
+    /// The dimension of an argument does not match the dimension of the set.
+    DimensionMismatch {
+        /// The dimension of the set.
+        expected: usize,
+        /// The dimension of the argument.
+        found: usize,
+    },
+}
+
+impl fmt::Display for SetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SetError::Infeasible => write!(f, "set is empty"),
+            SetError::Unbounded => write!(f, "set is unbounded in the given direction"),
+            SetError::SolverFailure => write!(f, "linear programming solver failed"),
+            SetError::NumericConversion => write!(f, "numeric conversion failed"),
+            SetError::InvalidTolerance => write!(f, "tolerance must be positive"),
+            SetError::InvalidKrylovDimension => {
+                write!(f, "Krylov subspace dimension must be positive")
+            }
+            SetError::NotConverged => write!(f, "iteration limit reached before convergence"),
+            SetError::NotPolyhedral => write!(f, "set is not polyhedral"),
+            SetError::DimensionMismatch { expected, found } => write!(
+                f,
+                "dimension mismatch: expected {}, found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SetError {}
@@ -0,0 +1,185 @@
+/*!
+ * Memoized support function evaluations.
+ */
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use crate::convex::{expect_support, DirectionMatrix, LazySet};
+use crate::error::SetError;
+use nalgebra::{DMatrix, DVector, RealField, SVector};
+
+/// The result of a support function evaluation.
+type SupportResult<N, const D: usize> = Result<(N, SVector<N, D>), SetError>;
+
+/// A set that remembers its support function evaluations per direction.
+///
+/// Repeated evaluations in the same direction, for example of a set shared by several branches
+/// of an expression, return the remembered result instead of evaluating the set again.
+/// Directions are compared exactly by the bits of their entries, see [`CacheKey`], so this pays
+/// off for fixed templates of directions.
+pub struct Cached<S, N, const D: usize> {
+    /// The cached set.
+    set: S,
+    /// The results by the bits of the direction entries.
+    cache: Mutex<HashMap<Vec<u64>, SupportResult<N, D>>>,
+}
+
+impl<S, N, const D: usize> Cached<S, N, D> {
+    /// Create a new cache around a set.
+    pub fn new(set: S) -> Cached<S, N, D> {
+        Cached {
+            set,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The cached set.
+    pub fn inner(&self) -> &S {
+        &self.set
+    }
+
+    /// The number of remembered directions.
+    pub fn cache_len(&self) -> usize {
+        self.cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns true if no direction is remembered, unlike [`LazySet::is_empty`] which tells
+    /// whether the set is empty.
+    pub fn is_cache_empty(&self) -> bool {
+        self.cache_len() == 0
+    }
+
+    /// Forget all remembered directions.
+    pub fn clear(&self) {
+        self.cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+impl<S, N, const D: usize> LazySet<N, D> for Cached<S, N, D>
+where
+    S: LazySet<N, D>,
+    N: RealField + CacheKey,
+{
+    fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &SVector<N, D>) -> Result<(N, SVector<N, D>), SetError> {
+        let key = direction_key(direction);
+        if let Some(result) = self
+            .cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+        {
+            return *result;
+        }
+
+        // evaluate without holding the lock, so other directions can be evaluated meanwhile
+        let result = self.set.try_support(direction);
+        let _ = self
+            .cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key, result);
+        result
+    }
+
+    fn try_support_batch(
+        &self,
+        directions: &DirectionMatrix<N, D>,
+    ) -> Vec<Result<(N, SVector<N, D>), SetError>> {
+        let keys: Vec<_> = directions
+            .column_iter()
+            .map(|direction| direction_key(&direction.into_owned()))
+            .collect();
+        let hits: Vec<_> = {
+            let cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+            keys.iter().map(|key| cache.get(key).copied()).collect()
+        };
+        let misses: Vec<_> = (0..hits.len()).filter(|&j| hits[j].is_none()).collect();
+        if misses.is_empty() {
+            return hits.into_iter().flatten().collect();
+        }
+
+        // evaluate all misses in one batch, so the set can share work between them
+        let columns: Vec<_> = misses.iter().map(|&j| directions.column(j)).collect();
+        let evaluated = self
+            .set
+            .try_support_batch(&DirectionMatrix::from_columns(&columns));
+        {
+            let mut cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+            for (&j, result) in misses.iter().zip(&evaluated) {
+                let _ = cache.insert(keys[j].clone(), *result);
+            }
+        }
+        let mut evaluated = evaluated.into_iter();
+        hits.into_iter()
+            .flat_map(|hit| hit.or_else(|| evaluated.next()))
+            .collect()
+    }
+
+    fn constraints(&self) -> Option<(DMatrix<N>, DVector<N>)> {
+        self.set.constraints()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    fn is_member(&self, point: &SVector<N, D>) -> bool {
+        self.set.is_member(point)
+    }
+}
+
+/// A scalar whose bits identify it exactly, so that directions can key the cache of [`Cached`].
+///
+/// It is implemented for `f32` and `f64`. A conversion to a narrower type would let distinct
+/// directions share the remembered result of one of them.
+pub trait CacheKey: Copy {
+    /// The bits of the value, with -0 and 0 identified.
+    fn key_bits(self) -> u64;
+}
+
+impl CacheKey for f32 {
+    fn key_bits(self) -> u64 {
+        u64::from((self + 0.0).to_bits())
+    }
+}
+
+impl CacheKey for f64 {
+    fn key_bits(self) -> u64 {
+        (self + 0.0).to_bits()
+    }
+}
+
+/// The bits of the entries of a direction.
+fn direction_key<N, const D: usize>(direction: &SVector<N, D>) -> Vec<u64>
+where
+    N: RealField + CacheKey,
+{
+    direction.iter().map(|x| x.key_bits()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Vector2;
+
+    use crate::convex::{Hypersphere, LazySet};
+
+    #[test]
+    fn cache_doesnt_shadow_emptiness() {
+        let cached = Hypersphere::new(1.0, Vector2::<f64>::zeros()).cached();
+        assert!(cached.is_cache_empty());
+        assert!(!cached.is_empty());
+        let _ = cached.support(&Vector2::new(1.0, 0.0));
+        assert_eq!(cached.cache_len(), 1);
+        assert!(!cached.is_cache_empty());
+    }
+}
@@ -0,0 +1,344 @@
+/*!
+ * The matrix exponential map operation.
+ */
+use nalgebra::{DMatrix, DVector, RealField, SMatrix, SVector};
+
+use crate::convex::{d_expect_support, expect_support, DLazySet, DSharedSet, LazySet, SharedSet};
+use crate::error::SetError;
+
+/// How the action of the matrix exponential on a vector is computed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExpMethod {
+    /// Form e^{At} once with a Padé approximation and multiply with it.
+    Pade,
+    /// Approximate e^{At} v in a Krylov subspace of the given dimension by the Arnoldi process,
+    /// using only matrix-vector products with A. The approximation improves with the dimension,
+    /// which must be positive. Support queries report [`SetError::NotConverged`] where the error
+    /// estimate of the approximation shows that the dimension is too small. See [`DExponentialMap::with_operator`] for matrices that are only
+    /// given by these products.
+    Krylov(usize),
+}
+
+/// The image e^{At} X of a set under the matrix exponential.
+///
+/// The support function is rho(d) = rho_X(e^{A^T t} d), where e^{At} is formed once on creation.
+pub struct ExponentialMap<N, const D: usize> {
+    /// The matrix exponential e^{At}.
+    exp_transform: SMatrix<N, D, D>,
+    /// The support function.
+    sf: SharedSet<N, D>,
+}
+
+impl<N, const D: usize> ExponentialMap<N, D>
+where
+    N: RealField + Copy,
+{
+    /// Create a new exponential map e^{At} of a set.
+    pub fn new(
+        a_transform: SMatrix<N, D, D>,
+        time: N,
+        sf: SharedSet<N, D>,
+    ) -> ExponentialMap<N, D> {
+        // nalgebra only implements exp for statically sized matrices with a typenum dimension
+        let exp_transform = DMatrix::from_column_slice(D, D, (a_transform * time).as_slice()).exp();
+        ExponentialMap {
+            exp_transform: SMatrix::from_column_slice(exp_transform.as_slice()),
+            sf,
+        }
+    }
+}
+
+impl<N, const D: usize> LazySet<N, D> for ExponentialMap<N, D>
+where
+    N: RealField + Copy,
+{
+    fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &SVector<N, D>) -> Result<(N, SVector<N, D>), SetError> {
+        let (d, p) = self
+            .sf
+            .try_support(&(self.exp_transform.transpose() * direction))?;
+        Ok((d, self.exp_transform * p))
+    }
+}
+
+/// A square linear map A given by its products with vectors, e.g. a sparse matrix.
+pub trait LinearOperator<N> {
+    /// The dimension of the vectors the map acts on.
+    fn dimension(&self) -> usize;
+
+    /// The product A v.
+    fn apply(&self, v: &DVector<N>) -> DVector<N>;
+
+    /// The product A^T v.
+    fn apply_transpose(&self, v: &DVector<N>) -> DVector<N>;
+}
+
+impl<N> LinearOperator<N> for DMatrix<N>
+where
+    N: RealField + Copy,
+{
+    fn dimension(&self) -> usize {
+        self.nrows()
+    }
+
+    fn apply(&self, v: &DVector<N>) -> DVector<N> {
+        self * v
+    }
+
+    fn apply_transpose(&self, v: &DVector<N>) -> DVector<N> {
+        self.tr_mul(v)
+    }
+}
+
+/// The image e^{At} X of a dynamically sized set under the matrix exponential.
+///
+/// With a Krylov action the support value rho_X(e^{A^T t} d) and the support vector e^{At} p are
+/// approximated separately, from products with A^T and with A, so d^T e^{At} p equals the value only
+/// up to the error of the approximation.
+pub struct DExponentialMap<N> {
+    /// The action of e^{At}.
+    action: ExpAction<N>,
+    /// The support function.
+    sf: DSharedSet<N>,
+}
+
+/// The action of e^{At} and its transpose on vectors.
+enum ExpAction<N> {
+    /// The matrix exponential e^{At}.
+    Dense(DMatrix<N>),
+    /// A and t, for the Arnoldi process with the given subspace dimension.
+    Krylov {
+        operator: Box<dyn LinearOperator<N> + Send + Sync>,
+        time: N,
+        dimension: usize,
+    },
+}
+
+impl<N> DExponentialMap<N>
+where
+    N: RealField + Copy,
+{
+    /// Create a new exponential map e^{At} of a set, with the action computed by `method`.
+    ///
+    /// Reports [`SetError::DimensionMismatch`] if A is not square and
+    /// [`SetError::InvalidKrylovDimension`] if the dimension of a Krylov subspace is zero.
+    pub fn new(
+        a_transform: DMatrix<N>,
+        time: N,
+        method: ExpMethod,
+        sf: DSharedSet<N>,
+    ) -> Result<DExponentialMap<N>, SetError> {
+        if !a_transform.is_square() {
+            return Err(SetError::DimensionMismatch {
+                expected: a_transform.nrows(),
+                found: a_transform.ncols(),
+            });
+        }
+        match method {
+            ExpMethod::Pade => Ok(DExponentialMap {
+                action: ExpAction::Dense((a_transform * time).exp()),
+                sf,
+            }),
+            ExpMethod::Krylov(dimension) => {
+                DExponentialMap::with_operator(a_transform, time, dimension, sf)
+            }
+        }
+    }
+
+    /// Create a new exponential map e^{At} of a set, with the action approximated in a Krylov
+    /// subspace of the given dimension from products with A only.
+    ///
+    /// Reports [`SetError::InvalidKrylovDimension`] if the dimension is zero.
+    pub fn with_operator<A>(
+        operator: A,
+        time: N,
+        dimension: usize,
+        sf: DSharedSet<N>,
+    ) -> Result<DExponentialMap<N>, SetError>
+    where
+        A: LinearOperator<N> + Send + Sync + 'static,
+    {
+        if dimension == 0 {
+            return Err(SetError::InvalidKrylovDimension);
+        }
+        Ok(DExponentialMap {
+            action: ExpAction::Krylov {
+                operator: Box::new(operator),
+                time,
+                dimension,
+            },
+            sf,
+        })
+    }
+}
+
+impl<N> DLazySet<N> for DExponentialMap<N>
+where
+    N: RealField + Copy,
+{
+    fn support(&self, direction: &DVector<N>) -> (N, DVector<N>) {
+        d_expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &DVector<N>) -> Result<(N, DVector<N>), SetError> {
+        let dim = match &self.action {
+            ExpAction::Dense(exp_transform) => exp_transform.nrows(),
+            ExpAction::Krylov { operator, .. } => operator.dimension(),
+        };
+        if direction.len() != dim {
+            return Err(SetError::DimensionMismatch {
+                expected: dim,
+                found: direction.len(),
+            });
+        }
+
+        let (d, p) = match &self.action {
+            ExpAction::Dense(exp_transform) => {
+                self.sf.try_support(&exp_transform.tr_mul(direction))
+            }
+            ExpAction::Krylov {
+                operator,
+                time,
+                dimension,
+            } => self.sf.try_support(&krylov_expv(
+                |v| operator.apply_transpose(v),
+                *time,
+                direction,
+                *dimension,
+            )?),
+        }?;
+        if p.len() != dim {
+            return Err(SetError::DimensionMismatch {
+                expected: dim,
+                found: p.len(),
+            });
+        }
+
+        let point = match &self.action {
+            ExpAction::Dense(exp_transform) => exp_transform * p,
+            ExpAction::Krylov {
+                operator,
+                time,
+                dimension,
+            } => krylov_expv(|v| operator.apply(v), *time, &p, *dimension)?,
+        };
+        Ok((d, point))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.sf.is_empty()
+    }
+}
+
+/// Approximate e^{At} v in the Krylov subspace span{v, Av, ..., A^{m-1} v}.
+///
+/// The Arnoldi process gives an orthonormal basis V_m and a Hessenberg matrix H_m = V_m^T A V_m,
+/// and e^{At} v is approximated by |v| V_m e^{H_m t} e_1. Reports [`SetError::NotConverged`] if the
+/// a-posteriori error estimate h_{m+1,m} |e_m^T e^{H_m t} e_1| |v| exceeds sqrt(eps) times the
+/// larger of |v| and the approximation, as the subspace is then too small to be trusted.
+fn krylov_expv<N, F>(
+    apply: F,
+    time: N,
+    v: &DVector<N>,
+    dimension: usize,
+) -> Result<DVector<N>, SetError>
+where
+    N: RealField + Copy,
+    F: Fn(&DVector<N>) -> DVector<N>,
+{
+    let beta = v.norm();
+    if beta.is_zero() {
+        return Ok(v.clone());
+    }
+
+    let m = dimension.min(v.len());
+    let mut basis = vec![v / beta];
+    let mut hessenberg = DMatrix::<N>::zeros(m, m);
+    let mut k = m;
+    let mut scale = N::zero();
+    // h_{m+1,m}, the part of A v_m outside of the subspace
+    let mut residual = N::zero();
+    for j in 0..m {
+        let mut w = apply(&basis[j]);
+        scale = scale.max(w.norm());
+        for (i, q) in basis.iter().enumerate() {
+            hessenberg[(i, j)] = w.dot(q);
+            w.axpy(-hessenberg[(i, j)], q, N::one());
+        }
+        let norm = w.norm();
+        // the subspace is invariant under A, so the approximation is exact
+        if norm <= N::default_epsilon() * scale {
+            k = j + 1;
+            break;
+        }
+        if j + 1 < m {
+            hessenberg[(j + 1, j)] = norm;
+            basis.push(w / norm);
+        } else {
+            residual = norm;
+        }
+    }
+
+    let exp_h = (hessenberg.slice((0, 0), (k, k)).into_owned() * time).exp();
+    let size = exp_h.column(0).norm().max(N::one());
+    if residual * exp_h[(k - 1, 0)].abs() > N::default_epsilon().sqrt() * size {
+        return Err(SetError::NotConverged);
+    }
+    let mut result = DVector::<N>::zeros(v.len());
+    for (i, q) in basis.iter().take(k).enumerate() {
+        result.axpy(beta * exp_h[(i, 0)], q, N::one());
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use nalgebra::{DMatrix, DVector};
+
+    use super::{DExponentialMap, ExpMethod};
+    use crate::convex::{DHalfspacePolytope, DLazySet, DSharedSet};
+    use crate::error::SetError;
+
+    #[test]
+    fn empty_krylov_subspace() {
+        let square = DHalfspacePolytope::new(
+            DMatrix::from_row_slice(4, 2, &[1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0]),
+            DVector::from_element(4, 1.0),
+        );
+        let a_transform = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, -1.0, 0.0]);
+        let map = DExponentialMap::new(a_transform, 1.0, ExpMethod::Krylov(0), Arc::new(square));
+        assert_eq!(map.err(), Some(SetError::InvalidKrylovDimension));
+    }
+
+    #[test]
+    fn small_krylov_subspace() {
+        let square = DHalfspacePolytope::new(
+            DMatrix::from_row_slice(4, 2, &[1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0]),
+            DVector::from_column_slice(&[1.1, 0.1, 0.1, 0.1]),
+        );
+        let square: DSharedSet<f64> = Arc::new(square);
+        let rotation = DMatrix::from_row_slice(2, 2, &[0.0, -1.0, 1.0, 0.0]);
+        let time = std::f64::consts::FRAC_PI_2;
+        let direction = DVector::from_column_slice(&[0.0, 1.0]);
+
+        // one dimension can't represent the rotation
+        let map =
+            DExponentialMap::new(rotation.clone(), time, ExpMethod::Krylov(1), square.clone());
+        assert_eq!(
+            map.unwrap().try_support(&direction).err(),
+            Some(SetError::NotConverged)
+        );
+
+        let exact = DExponentialMap::new(rotation.clone(), time, ExpMethod::Pade, square.clone());
+        let (expected, _) = exact.unwrap().try_support(&direction).unwrap();
+        assert!((expected - 1.1).abs() < 1e-9, "{}", expected);
+        let map = DExponentialMap::new(rotation, time, ExpMethod::Krylov(2), square);
+        let (value, _) = map.unwrap().try_support(&direction).unwrap();
+        assert!((value - expected).abs() < 1e-9, "{}", value);
+    }
+}
@@ -3,22 +3,20 @@
  */
 use nalgebra::{RealField, SVector};
 
-use crate::convex::LazySet;
+use crate::convex::{expect_support, DirectionMatrix, LazySet, SharedSet};
+use crate::error::SetError;
 
 /// Convex hull of two convex sets.
 pub struct ConvexHull<N, const D: usize> {
     /// The first support function (left hand side).
-    lhs: Box<dyn LazySet<N, D>>,
+    lhs: SharedSet<N, D>,
     /// The second support function (right hand side).
-    rhs: Box<dyn LazySet<N, D>>,
+    rhs: SharedSet<N, D>,
 }
 
 impl<N, const D: usize> ConvexHull<N, D> {
     /// Create a new convex hull of two convex sets.
-    pub fn new(
-        lhs: Box<dyn LazySet<N, D>>,
-        rhs: Box<dyn LazySet<N, D>>,
-    ) -> ConvexHull<N, D> {
+    pub fn new(lhs: SharedSet<N, D>, rhs: SharedSet<N, D>) -> ConvexHull<N, D> {
         ConvexHull { lhs, rhs }
     }
 }
@@ -28,12 +26,142 @@ where
     N: RealField,
 {
     fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
-        let (d1, p1) = self.lhs.support(direction);
-        let (d2, p2) = self.rhs.support(direction);
-        if d1 > d2 {
-            (d1, p1)
-        } else {
-            (d2, p2)
+        expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &SVector<N, D>) -> Result<(N, SVector<N, D>), SetError> {
+        hull_supports(
+            self.lhs.try_support(direction),
+            self.rhs.try_support(direction),
+        )
+    }
+
+    fn try_support_batch(
+        &self,
+        directions: &DirectionMatrix<N, D>,
+    ) -> Vec<Result<(N, SVector<N, D>), SetError>> {
+        self.lhs
+            .try_support_batch(directions)
+            .into_iter()
+            .zip(self.rhs.try_support_batch(directions))
+            .map(|(s1, s2)| hull_supports(s1, s2))
+            .collect()
+    }
+}
+
+/// The support of a hull from the supports of the two operands.
+fn hull_supports<N, const D: usize>(
+    s1: Result<(N, SVector<N, D>), SetError>,
+    s2: Result<(N, SVector<N, D>), SetError>,
+) -> Result<(N, SVector<N, D>), SetError>
+where
+    N: RealField,
+{
+    match (s1, s2) {
+        // the hull with an empty set is the other set
+        (Err(SetError::Infeasible), s) | (s, Err(SetError::Infeasible)) => s,
+        (s1, s2) => {
+            let (d1, p1) = s1?;
+            let (d2, p2) = s2?;
+            if d1 > d2 {
+                Ok((d1, p1))
+            } else {
+                Ok((d2, p2))
+            }
+        }
+    }
+}
+
+/// Convex hull of an array of convex sets.
+///
+/// The hull of no sets is the empty set.
+pub struct ConvexHullArray<N, const D: usize> {
+    /// The support functions of the sets.
+    sfs: Vec<SharedSet<N, D>>,
+}
+
+impl<N, const D: usize> ConvexHullArray<N, D> {
+    /// Create a new convex hull of an array of sets.
+    pub fn new(sfs: Vec<SharedSet<N, D>>) -> ConvexHullArray<N, D> {
+        ConvexHullArray { sfs }
+    }
+
+    /// Add a set to the hull.
+    pub fn push(&mut self, sf: SharedSet<N, D>) {
+        self.sfs.push(sf);
+    }
+}
+
+impl<N, const D: usize> LazySet<N, D> for ConvexHullArray<N, D>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &SVector<N, D>) -> Result<(N, SVector<N, D>), SetError> {
+        // the empty set is the neutral element of the hull
+        let mut hull = Err(SetError::Infeasible);
+        for sf in self.sfs.iter() {
+            hull = hull_supports(hull, sf.try_support(direction));
+        }
+        hull
+    }
+
+    fn try_support_batch(
+        &self,
+        directions: &DirectionMatrix<N, D>,
+    ) -> Vec<Result<(N, SVector<N, D>), SetError>> {
+        let empty = (0..directions.ncols())
+            .map(|_| Err(SetError::Infeasible))
+            .collect();
+        self.sfs.iter().fold(empty, |hulls: Vec<_>, sf| {
+            hulls
+                .into_iter()
+                .zip(sf.try_support_batch(directions))
+                .map(|(hull, s)| hull_supports(hull, s))
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use nalgebra::Vector2;
+
+    use super::ConvexHullArray;
+    use crate::convex::{
+        DirectionMatrix, EmptySet, HalfSpace, Hyperrectangle, Hypersphere, LazySet, SharedSet,
+    };
+
+    #[test]
+    fn batch_matches_single_supports() {
+        let square: SharedSet<f64, 2> = Arc::new(Hyperrectangle::new(
+            Vector2::new(1.0, 0.0),
+            Vector2::new(1.0, 2.0),
+        ));
+        let disk: SharedSet<f64, 2> = Arc::new(Hypersphere::new(1.5, Vector2::new(-1.0, 1.0)));
+        let halfspace: SharedSet<f64, 2> = Arc::new(HalfSpace::new(Vector2::new(1.0, 1.0), 0.5));
+        let directions = DirectionMatrix::<f64, 2>::from_columns(&[
+            Vector2::new(1.0, 0.0),
+            Vector2::new(-1.0, 2.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(0.0, 0.0),
+        ]);
+        for sets in [
+            vec![],
+            vec![square.clone(), disk.clone()],
+            vec![square.clone(), halfspace.clone(), disk.clone()],
+            vec![square, Arc::new(EmptySet::new()), halfspace],
+        ] {
+            let set = ConvexHullArray::new(sets);
+            let batch = set.try_support_batch(&directions);
+            for (result, direction) in batch.into_iter().zip(directions.column_iter()) {
+                assert_eq!(result, set.try_support(&direction.into_owned()));
+            }
         }
     }
 }
@@ -0,0 +1,667 @@
+/*!
+ * Intersection
+ */
+use nalgebra::{DMatrix, DVector, RealField, SVector};
+use num_traits::ToPrimitive;
+
+use crate::check::is_disjoint_with_solver;
+use crate::convex::{expect_support, DHalfspacePolytope, DLazySet, LazySet, SharedSet};
+use crate::error::SetError;
+use crate::lp::{to_f64_vec, LpSolver, MiniLp};
+
+/// The maximum number of step doublings when bracketing a line search.
+const MAX_DOUBLINGS: usize = 40;
+
+/// The number of golden section steps of a line search.
+const GOLDEN_STEPS: usize = 64;
+
+/// The maximum number of line searches when neither operand is polyhedral.
+const DESCENT_STEPS: usize = 32;
+
+/// The maximum number of columns added when one operand is polyhedral.
+const COLUMN_STEPS: usize = 256;
+
+/// The intersection of two convex sets.
+///
+/// The support function of an intersection is the infimal convolution
+/// rho(d) = inf_u rho_X(d - u) + rho_Y(u), which is computed depending on the operands:
+/// - if both operands are polyhedral (see [`LazySet::constraints`]), their constraints are stacked
+///   and the support function is solved exactly as an LP,
+/// - if one operand is polyhedral with constraints Ax <= b, the other operand X gives
+///   rho(d) = min_{lambda >= 0} rho_X(d - A^T lambda) + lambda^T b, which is minimized by column
+///   generation over the support points of X and is exact for polytopes; the support vector is a
+///   convex combination of support points of X within the constraints and thus lies in the
+///   intersection,
+/// - otherwise a sequence of line searches along rho_X(d - u - lambda c) + rho_Y(u + lambda c) gives
+///   an upper bound, with a point of the first operand that attains it approximately as the support
+///   vector.
+///
+/// Disjoint operands that aren't polyhedral are not always detected.
+pub struct Intersection<N, const D: usize, S = MiniLp> {
+    /// The first set.
+    lhs: SharedSet<N, D>,
+    /// The second set.
+    rhs: SharedSet<N, D>,
+    /// The solver for the LPs of polyhedral operands.
+    solver: S,
+}
+
+impl<N, const D: usize> Intersection<N, D> {
+    /// Create a new intersection of two sets.
+    pub fn new(lhs: SharedSet<N, D>, rhs: SharedSet<N, D>) -> Intersection<N, D> {
+        Intersection {
+            lhs,
+            rhs,
+            solver: MiniLp,
+        }
+    }
+}
+
+impl<N, const D: usize, S> Intersection<N, D, S> {
+    /// Use a different LP solver for polyhedral operands.
+    pub fn with_solver<T: LpSolver>(self, solver: T) -> Intersection<N, D, T> {
+        Intersection {
+            lhs: self.lhs,
+            rhs: self.rhs,
+            solver,
+        }
+    }
+}
+
+impl<N, const D: usize, S> Intersection<N, D, S>
+where
+    N: RealField + Copy + ToPrimitive,
+    S: LpSolver + Clone,
+{
+    /// The support function of a set intersected with the halfspaces Ax <= b, reporting
+    /// [`SetError::Infeasible`] if no support point within the halfspaces was found because they
+    /// are disjoint from the set.
+    fn halfspaces_support(
+        &self,
+        set: &dyn LazySet<N, D>,
+        a_transform: &DMatrix<N>,
+        b: &DVector<N>,
+        direction: &SVector<N, D>,
+    ) -> Result<(N, SVector<N, D>), SetError> {
+        match halfspaces_support(set, a_transform, b, direction, &self.solver) {
+            Err(SetError::NotConverged) if self.is_empty() => Err(SetError::Infeasible),
+            support => support,
+        }
+    }
+}
+
+impl<N, const D: usize, S> LazySet<N, D> for Intersection<N, D, S>
+where
+    N: RealField + Copy + ToPrimitive,
+    S: LpSolver + Clone,
+{
+    fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &SVector<N, D>) -> Result<(N, SVector<N, D>), SetError> {
+        match (self.lhs.constraints(), self.rhs.constraints()) {
+            (Some(lhs), Some(rhs)) => {
+                let (a_transform, b) = stack_constraints::<N, D>(lhs, rhs);
+                let (value, point) = DHalfspacePolytope::new(a_transform, b)
+                    .with_solver(self.solver.clone())
+                    .try_support(&DVector::from_column_slice(direction.as_slice()))?;
+                Ok((value, SVector::from_column_slice(point.as_slice())))
+            }
+            (None, Some((a_transform, b))) => {
+                self.halfspaces_support(&*self.lhs, &a_transform, &b, direction)
+            }
+            (Some((a_transform, b)), None) => {
+                self.halfspaces_support(&*self.rhs, &a_transform, &b, direction)
+            }
+            (None, None) => descent_support(&*self.lhs, &*self.rhs, direction),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        is_disjoint_with_solver(&*self.lhs, &*self.rhs, self.solver.clone())
+    }
+
+    fn is_member(&self, point: &SVector<N, D>) -> bool {
+        self.lhs.is_member(point) && self.rhs.is_member(point)
+    }
+
+    fn constraints(&self) -> Option<(DMatrix<N>, DVector<N>)> {
+        Some(stack_constraints::<N, D>(
+            self.lhs.constraints()?,
+            self.rhs.constraints()?,
+        ))
+    }
+}
+
+/// Stack the constraints of two H-representations.
+pub(crate) fn stack_constraints<N, const D: usize>(
+    lhs: (DMatrix<N>, DVector<N>),
+    rhs: (DMatrix<N>, DVector<N>),
+) -> (DMatrix<N>, DVector<N>)
+where
+    N: RealField + Copy,
+{
+    let ((a1, b1), (a2, b2)) = (lhs, rhs);
+    let rows = a1.nrows();
+    let a_transform = DMatrix::<N>::from_fn(rows + a2.nrows(), D, |i, j| {
+        if i < rows {
+            a1[(i, j)]
+        } else {
+            a2[(i - rows, j)]
+        }
+    });
+    let b = DVector::<N>::from_fn(
+        rows + b2.len(),
+        |i, _| {
+            if i < rows {
+                b1[i]
+            } else {
+                b2[i - rows]
+            }
+        },
+    );
+    (a_transform, b)
+}
+
+/// The support value of a set, with unbounded directions giving `+inf`.
+fn support_value<N, const D: usize>(
+    set: &dyn LazySet<N, D>,
+    direction: &SVector<N, D>,
+) -> Result<N, SetError>
+where
+    N: RealField,
+{
+    match set.try_support(direction) {
+        Ok((value, _)) => Ok(value),
+        Err(SetError::Unbounded) => Ok(nalgebra::convert(f64::INFINITY)),
+        Err(e) => Err(e),
+    }
+}
+
+/// The support function of a set X intersected with the halfspaces Ax <= b.
+///
+/// The value is the dual bound min_{lambda >= 0} rho_X(d - A^T lambda) + lambda^T b, minimized by
+/// column generation: the LP over the convex hull of the support points of X collected so far gives
+/// a lower bound and multipliers lambda, and the support point of X in the direction d - A^T lambda
+/// gives an upper bound and a new column. This stops when the bounds meet, which happens after
+/// finitely many steps for polytopes. The support vector is the solution of the last LP, so it lies
+/// in the intersection even if the bounds haven't met yet. If X is unbounded in all of the
+/// directions sampled, the columns start from its support point in the zero direction. If X is
+/// unbounded in every dual direction, the support value of the halfspaces is returned as the upper
+/// bound. That value is sound but may not be tight, and the support vector then doesn't attain it.
+fn halfspaces_support<N, S, const D: usize>(
+    set: &dyn LazySet<N, D>,
+    a_transform: &DMatrix<N>,
+    b: &DVector<N>,
+    direction: &SVector<N, D>,
+    solver: &S,
+) -> Result<(N, SVector<N, D>), SetError>
+where
+    N: RealField + Copy + ToPrimitive,
+    S: LpSolver,
+{
+    let mut columns = Columns::new(set, a_transform, b)?;
+    let _ = columns.sample(direction)?;
+    if columns.points.is_empty() {
+        // X is unbounded in all directions sampled so far, but bounded in the zero direction
+        let _ = columns.sample(&SVector::zeros())?;
+    }
+    columns.find_feasible(solver)?;
+
+    let mut upper: N = nalgebra::convert(f64::INFINITY);
+    for _ in 0..COLUMN_STEPS {
+        let (lower, lambda) = columns.dual(solver, direction, false)?;
+        let (value, added) = columns.sample(&columns.dual_direction(direction, &lambda))?;
+        upper = upper.min(value + columns.offset(&lambda));
+        if !added || upper - lower <= N::default_epsilon().sqrt() * (N::one() + upper.abs()) {
+            break;
+        }
+    }
+    if !upper.is_finite() {
+        // rho(d) <= rho_P(d) for the polyhedron P of the halfspaces bounds the value when X is
+        // unbounded in every sampled direction
+        upper = polyhedron_value(a_transform, b, direction, solver)?;
+        if !upper.is_finite() {
+            return Err(SetError::Unbounded);
+        }
+    }
+    Ok((upper, columns.combination(solver, direction)?))
+}
+
+/// The support value of the halfspaces Ax <= b, `+inf` if they are unbounded in the direction.
+fn polyhedron_value<N, S, const D: usize>(
+    a_transform: &DMatrix<N>,
+    b: &DVector<N>,
+    direction: &SVector<N, D>,
+    solver: &S,
+) -> Result<N, SetError>
+where
+    N: RealField + Copy + ToPrimitive,
+    S: LpSolver,
+{
+    let a_lp = DMatrix::from_vec(
+        a_transform.nrows(),
+        a_transform.ncols(),
+        to_f64_vec(a_transform.iter())?,
+    );
+    let b_lp = DVector::from_vec(to_f64_vec(b.iter())?);
+    let objective = DVector::from_vec(to_f64_vec(direction.iter())?);
+    match solver.maximize(&objective, &a_lp, &b_lp) {
+        Ok((value, _)) => Ok(nalgebra::convert(value)),
+        Err(SetError::Unbounded) => Ok(nalgebra::convert(f64::INFINITY)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Returns true if the set X is disjoint from the halfspaces Ax <= b, which is certified by a row
+/// with min_x a^T x > b or by multipliers lambda >= 0 with rho_X(-A^T lambda) + lambda^T b < 0.
+pub(crate) fn is_disjoint_from_halfspaces<N, S, const D: usize>(
+    set: &dyn LazySet<N, D>,
+    a_transform: &DMatrix<N>,
+    b: &DVector<N>,
+    solver: &S,
+) -> bool
+where
+    N: RealField + Copy + ToPrimitive,
+    S: LpSolver,
+{
+    let feasible = Columns::new(set, a_transform, b).and_then(|mut columns| {
+        let _ = columns.sample(&SVector::zeros())?;
+        columns.find_feasible(solver)
+    });
+    matches!(feasible, Err(SetError::Infeasible))
+}
+
+/// The support points of a set X collected as the columns of the LPs over their convex hull within
+/// the halfspaces Ax <= b.
+struct Columns<'a, N, const D: usize> {
+    /// The set X.
+    set: &'a dyn LazySet<N, D>,
+    /// The rows of A.
+    normals: Vec<SVector<N, D>>,
+    /// The upper bounds b.
+    upper_bounds: &'a DVector<N>,
+    /// The distinct support points of X.
+    points: Vec<SVector<N, D>>,
+}
+
+impl<'a, N, const D: usize> Columns<'a, N, D>
+where
+    N: RealField + Copy + ToPrimitive,
+{
+    /// Starts with the support points against each normal, reporting [`SetError::Infeasible`] if
+    /// one of the halfspaces misses the set.
+    fn new(
+        set: &'a dyn LazySet<N, D>,
+        a_transform: &DMatrix<N>,
+        upper_bounds: &'a DVector<N>,
+    ) -> Result<Columns<'a, N, D>, SetError> {
+        let normals = (0..a_transform.nrows())
+            .map(|i| SVector::<N, D>::from_fn(|j, _| a_transform[(i, j)]))
+            .collect();
+        let mut columns = Columns {
+            set,
+            normals,
+            upper_bounds,
+            points: Vec::new(),
+        };
+        for i in 0..columns.normals.len() {
+            let (normal, offset) = (columns.normals[i], upper_bounds[i]);
+            if normal.iter().all(|a| a.is_zero()) {
+                if offset < N::zero() {
+                    return Err(SetError::Infeasible);
+                }
+                continue;
+            }
+            // the set misses the halfspace if min_x a^T x = -rho(-a) > b
+            let (value, _) = columns.sample(&-normal)?;
+            if -value > offset {
+                return Err(SetError::Infeasible);
+            }
+        }
+        Ok(columns)
+    }
+
+    /// The support value of X in the given direction, `+inf` if X is unbounded, and whether the
+    /// support point is a new column.
+    fn sample(&mut self, direction: &SVector<N, D>) -> Result<(N, bool), SetError> {
+        let (value, point) = match self.set.try_support(direction) {
+            Ok(support) => support,
+            Err(SetError::Unbounded) => return Ok((nalgebra::convert(f64::INFINITY), false)),
+            Err(e) => return Err(e),
+        };
+        // repeated points make the LPs degenerate
+        let tol = N::default_epsilon().sqrt() * (N::one() + point.norm());
+        let added = self.points.iter().all(|p| (p - point).norm() > tol);
+        if added {
+            self.points.push(point);
+        }
+        Ok((value, added))
+    }
+
+    /// The direction d - A^T lambda.
+    fn dual_direction(&self, direction: &SVector<N, D>, lambda: &[N]) -> SVector<N, D> {
+        self.normals
+            .iter()
+            .zip(lambda)
+            .fold(*direction, |v, (normal, l)| v - normal * *l)
+    }
+
+    /// The offset lambda^T b.
+    fn offset(&self, lambda: &[N]) -> N {
+        self.upper_bounds
+            .iter()
+            .zip(lambda)
+            .fold(N::zero(), |sum, (b, l)| sum + *b * *l)
+    }
+
+    /// Adds columns until their convex hull meets the halfspaces, reporting
+    /// [`SetError::Infeasible`] if the multipliers of the zero direction certify that X misses them.
+    fn find_feasible<S: LpSolver>(&mut self, solver: &S) -> Result<(), SetError> {
+        let zero = SVector::<N, D>::zeros();
+        let scale = self
+            .upper_bounds
+            .iter()
+            .fold(N::zero(), |scale, b| scale.max(b.abs()));
+        let tol = N::default_epsilon().sqrt() * (N::one() + scale);
+        for _ in 0..COLUMN_STEPS {
+            let (lower, lambda) = self.dual(solver, &zero, true)?;
+            if lower >= -tol {
+                break;
+            }
+            let (value, added) = self.sample(&self.dual_direction(&zero, &lambda))?;
+            if value + self.offset(&lambda) < -tol {
+                return Err(SetError::Infeasible);
+            }
+            if !added {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Solves the dual of [`Columns::combination`],
+    /// min_{lambda >= 0} max_k (d - A^T lambda)^T p_k + lambda^T b, returning its value and the
+    /// multipliers. With `normalized` the multipliers sum to one, which keeps the LP bounded while
+    /// the convex hull of the columns misses the halfspaces.
+    fn dual<S: LpSolver>(
+        &self,
+        solver: &S,
+        direction: &SVector<N, D>,
+        normalized: bool,
+    ) -> Result<(N, Vec<N>), SetError> {
+        // the variables are lambda and t >= (d - A^T lambda)^T (p_k - p_0) + lambda^T (b - A p_0),
+        // shifted to the first column p_0 for the conditioning of the LP
+        let origin = *self.points.first().ok_or(SetError::NotConverged)?;
+        let rows = self.normals.len();
+        let constraints = rows + self.points.len() + if normalized { 2 } else { 0 };
+        let mut a_lp = DMatrix::<f64>::zeros(constraints, rows + 1);
+        let mut b_lp = DVector::<f64>::zeros(constraints);
+        for i in 0..rows {
+            a_lp[(i, i)] = -1.0;
+        }
+        for (k, point) in self.points.iter().enumerate() {
+            for (i, (normal, offset)) in self
+                .normals
+                .iter()
+                .zip(self.upper_bounds.iter())
+                .enumerate()
+            {
+                a_lp[(rows + k, i)] = to_f64(*offset - normal.dot(point))?;
+            }
+            a_lp[(rows + k, rows)] = -1.0;
+            b_lp[rows + k] = -to_f64(direction.dot(&(point - origin)))?;
+        }
+        if normalized {
+            let last = rows + self.points.len();
+            for i in 0..rows {
+                a_lp[(last, i)] = 1.0;
+                a_lp[(last + 1, i)] = -1.0;
+            }
+            b_lp[last] = 1.0;
+            b_lp[last + 1] = -1.0;
+        }
+        let mut objective = DVector::<f64>::zeros(rows + 1);
+        objective[rows] = -1.0;
+
+        let (value, solution) = solver
+            .maximize(&objective, &a_lp, &b_lp)
+            .map_err(|e| match e {
+                SetError::Infeasible | SetError::Unbounded => SetError::NotConverged,
+                e => e,
+            })?;
+        let lambda = solution
+            .iter()
+            .take(rows)
+            .map(|l| nalgebra::convert::<f64, N>(l.max(0.0)))
+            .collect();
+        Ok((
+            nalgebra::convert::<f64, N>(-value) + direction.dot(&origin),
+            lambda,
+        ))
+    }
+
+    /// The convex combination of the columns within the halfspaces maximizing d^T x.
+    ///
+    /// Reports [`SetError::NotConverged`] if no combination lies within the halfspaces.
+    fn combination<S: LpSolver>(
+        &self,
+        solver: &S,
+        direction: &SVector<N, D>,
+    ) -> Result<SVector<N, D>, SetError> {
+        // with x = p_0 + sum_k mu_k (p_k - p_0), the weights mu are nonnegative with
+        // sum_k mu_k <= 1 and a_i^T p_0 + sum_k mu_k a_i^T (p_k - p_0) <= b_i
+        let Some((origin, points)) = self.points.split_first() else {
+            return Err(SetError::NotConverged);
+        };
+        let (count, rows) = (points.len(), self.normals.len());
+        let mut a_lp = DMatrix::<f64>::zeros(count + 1 + rows, count);
+        let mut b_lp = DVector::<f64>::zeros(count + 1 + rows);
+        for (k, point) in points.iter().enumerate() {
+            a_lp[(k, k)] = -1.0;
+            a_lp[(count, k)] = 1.0;
+            for (i, normal) in self.normals.iter().enumerate() {
+                a_lp[(count + 1 + i, k)] = to_f64(normal.dot(&(point - origin)))?;
+            }
+        }
+        b_lp[count] = 1.0;
+        for (i, (normal, offset)) in self
+            .normals
+            .iter()
+            .zip(self.upper_bounds.iter())
+            .enumerate()
+        {
+            b_lp[count + 1 + i] = to_f64(*offset - normal.dot(origin))?;
+        }
+        let objective = points
+            .iter()
+            .map(|point| to_f64(direction.dot(&(point - origin))))
+            .collect::<Result<Vec<f64>, SetError>>()?;
+
+        let (_, weights) = solver
+            .maximize(&DVector::from_vec(objective), &a_lp, &b_lp)
+            .map_err(|e| match e {
+                SetError::Infeasible => SetError::NotConverged,
+                e => e,
+            })?;
+        Ok(points
+            .iter()
+            .zip(weights.iter())
+            .fold(*origin, |sum, (point, mu)| {
+                sum + (point - origin) * nalgebra::convert::<f64, N>(mu.max(0.0))
+            }))
+    }
+}
+
+/// Converts a scalar to f64 for a solver.
+fn to_f64<N: ToPrimitive>(x: N) -> Result<f64, SetError> {
+    x.to_f64().ok_or(SetError::NumericConversion)
+}
+
+/// Upper bound the support function of the intersection of two sets by line searches over the
+/// split u of the direction in rho_X(d - u) + rho_Y(u).
+fn descent_support<N, const D: usize>(
+    lhs: &dyn LazySet<N, D>,
+    rhs: &dyn LazySet<N, D>,
+    direction: &SVector<N, D>,
+) -> Result<(N, SVector<N, D>), SetError>
+where
+    N: RealField + Copy,
+{
+    let value = |u: &SVector<N, D>| -> Result<N, SetError> {
+        Ok(support_value(lhs, &(direction - u))? + support_value(rhs, u)?)
+    };
+
+    // start from the better of the bounds rho_X(d) and rho_Y(d)
+    let mut u = SVector::<N, D>::zeros();
+    if support_value(rhs, direction)? < support_value(lhs, direction)? {
+        u = *direction;
+    }
+
+    for _ in 0..DESCENT_STEPS {
+        let (_, p) = lhs.try_support(&(direction - u))?;
+        let (_, q) = rhs.try_support(&u)?;
+        // p - q is a descent direction; the support points agree at the optimum
+        let c = p - q;
+        if c.norm() <= N::default_epsilon() * (N::one() + p.norm()) {
+            break;
+        }
+        match minimize_on_ray(
+            |lambda| value(&(u + c * lambda)),
+            (N::one() + direction.norm()) / c.norm(),
+        )? {
+            Some((lambda, _)) if !lambda.is_zero() => u += c * lambda,
+            Some(_) => break,
+            None => {
+                // the bound decreases along c without limit if c separates the sets
+                if support_value(lhs, &-c)? + support_value(rhs, &c)? < N::zero() {
+                    return Err(SetError::Infeasible);
+                }
+                break;
+            }
+        }
+    }
+
+    let (rho, point) = lhs.try_support(&(direction - u))?;
+    let (sigma, _) = rhs.try_support(&u)?;
+    Ok((rho + sigma, point))
+}
+
+/// Minimize a convex function over lambda >= 0, returning the minimizer and the minimum.
+///
+/// The minimizer is bracketed by doubling the step from `scale` and then located by a golden
+/// section search. The function may be `+inf` outside of its domain. Returns `None` if the
+/// function is still decreasing after the maximum number of doublings.
+fn minimize_on_ray<N, F>(f: F, scale: N) -> Result<Option<(N, N)>, SetError>
+where
+    N: RealField + Copy,
+    F: Fn(N) -> Result<N, SetError>,
+{
+    let two = N::one() + N::one();
+    let (mut lo, mut mid, mut hi) = (N::zero(), N::zero(), scale);
+    let (mut f_mid, mut f_hi) = (f(mid)?, f(hi)?);
+    let mut doublings = 0;
+    while f_hi <= f_mid {
+        if doublings == MAX_DOUBLINGS {
+            return Ok(None);
+        }
+        lo = mid;
+        mid = hi;
+        f_mid = f_hi;
+        hi *= two;
+        f_hi = f(hi)?;
+        doublings += 1;
+    }
+
+    let ratio: N = nalgebra::convert((5f64.sqrt() - 1.0) / 2.0);
+    let (mut x1, mut x2) = (hi - (hi - lo) * ratio, lo + (hi - lo) * ratio);
+    let (mut f1, mut f2) = (f(x1)?, f(x2)?);
+    for _ in 0..GOLDEN_STEPS {
+        if f1 <= f2 {
+            hi = x2;
+            x2 = x1;
+            f2 = f1;
+            x1 = hi - (hi - lo) * ratio;
+            f1 = f(x1)?;
+        } else {
+            lo = x1;
+            x1 = x2;
+            f1 = f2;
+            x2 = lo + (hi - lo) * ratio;
+            f2 = f(x2)?;
+        }
+    }
+
+    let mut best = (mid, f_mid);
+    for (x, fx) in [(x1, f1), (x2, f2)] {
+        if fx < best.1 {
+            best = (x, fx);
+        }
+    }
+    Ok(Some(best))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use nalgebra::{Const, Dynamic, Matrix2, OMatrix, Vector2};
+
+    use super::Intersection;
+    use crate::convex::{
+        HalfSpace, Hyperrectangle, Hypersphere, LazySet, SHalfspacePolytope, Zonotope,
+    };
+    use crate::lazy_operation::MinkowskiSum;
+
+    #[test]
+    fn box_within_wedge() {
+        let generators = OMatrix::<f64, Const<2>, Dynamic>::identity(2);
+        let square = Zonotope::new(Vector2::zeros(), generators);
+        let wedge = SHalfspacePolytope::new(Matrix2::new(1.0, 1.0, 1.0, -1.0), Vector2::zeros());
+        let intersection = Intersection::new(Arc::new(square), Arc::new(wedge));
+        let (value, point) = intersection.try_support(&Vector2::new(1.0, 0.0)).unwrap();
+        assert!(value.abs() < 1e-6, "{}", value);
+        assert!(point.norm() < 1e-6, "{}", point);
+    }
+
+    #[test]
+    fn disk_within_box() {
+        let disk = Hypersphere::new(1.0, Vector2::zeros());
+        let square = Hyperrectangle::new(Vector2::zeros(), Vector2::new(2.0, 2.0));
+        let intersection = Intersection::new(Arc::new(disk), Arc::new(square));
+        let (value, _) = intersection.try_support(&Vector2::new(1.0, 1.0)).unwrap();
+        assert!((value - 2.0f64.sqrt()).abs() < 1e-6, "{}", value);
+    }
+
+    #[test]
+    fn unbounded_set_within_box() {
+        let halfspace = HalfSpace::new(Vector2::new(1.0, 0.0), 0.0f64);
+        let disk = Hypersphere::new(1.0, Vector2::zeros());
+        let rounded = MinkowskiSum::new(Arc::new(halfspace), Arc::new(disk));
+        let square = Hyperrectangle::new(Vector2::zeros(), Vector2::new(3.0, 3.0));
+        let intersection = Intersection::new(Arc::new(rounded), Arc::new(square));
+        let (value, point) = intersection.try_support(&Vector2::new(-1.0, 0.0)).unwrap();
+        assert!((value - 3.0).abs() < 1e-6, "{}", value);
+        assert!(intersection.is_member(&point), "{}", point);
+    }
+
+    #[test]
+    fn rotated_unbounded_set_within_box() {
+        // bounded only along (1, 1), which none of the box normals hits
+        let halfspace = HalfSpace::new(Vector2::new(1.0, 1.0), 0.0f64);
+        let disk = Hypersphere::new(1.0, Vector2::zeros());
+        let rounded = MinkowskiSum::new(Arc::new(halfspace), Arc::new(disk));
+        let square = Hyperrectangle::new(Vector2::zeros(), Vector2::new(3.0, 3.0));
+        let intersection = Intersection::new(Arc::new(rounded), Arc::new(square));
+        for (direction, expected) in [
+            (Vector2::new(1.0, 0.0), 3.0),
+            (Vector2::new(-1.0, 0.0), 3.0),
+            (Vector2::new(1.0, 1.0), 2.0f64.sqrt()),
+        ] {
+            let (value, point) = intersection.try_support(&direction).unwrap();
+            assert!((value - expected).abs() < 1e-6, "{}: {}", direction, value);
+            assert!(intersection.is_member(&point), "{}", point);
+        }
+    }
+}
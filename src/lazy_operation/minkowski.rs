@@ -3,23 +3,21 @@
  */
 use nalgebra::{RealField, SVector};
 
-use crate::convex::LazySet;
+use crate::convex::{expect_support, DirectionMatrix, LazySet, SharedSet};
+use crate::error::SetError;
 
 /// A Minkowski sum of two convex sets.
 /// The Minkowski sum of two convex sets A and B is the set of all possible sums a + b where a ∈ A and b ∈ B.
 pub struct MinkowskiSum<N, const D: usize> {
     /// The first support function.
-    sf1: Box<dyn LazySet<N, D>>,
+    sf1: SharedSet<N, D>,
     ///  The second support function.
-    sf2: Box<dyn LazySet<N, D>>,
+    sf2: SharedSet<N, D>,
 }
 
 impl<N, const D: usize> MinkowskiSum<N, D> {
     /// Create a new Minkowski sum of sets with support functions.
-    pub fn new(
-        sf1: Box<dyn LazySet<N, D>>,
-        sf2: Box<dyn LazySet<N, D>>,
-    ) -> MinkowskiSum<N, D> {
+    pub fn new(sf1: SharedSet<N, D>, sf2: SharedSet<N, D>) -> MinkowskiSum<N, D> {
         MinkowskiSum { sf1, sf2 }
     }
 }
@@ -29,8 +27,135 @@ where
     N: RealField,
 {
     fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
-        let (d1, p1) = self.sf1.support(direction);
-        let (d2, p2) = self.sf2.support(direction);
-        (d1 + d2, p1 + p2)
+        expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &SVector<N, D>) -> Result<(N, SVector<N, D>), SetError> {
+        sum_supports(
+            self.sf1.try_support(direction),
+            self.sf2.try_support(direction),
+        )
+    }
+
+    fn try_support_batch(
+        &self,
+        directions: &DirectionMatrix<N, D>,
+    ) -> Vec<Result<(N, SVector<N, D>), SetError>> {
+        self.sf1
+            .try_support_batch(directions)
+            .into_iter()
+            .zip(self.sf2.try_support_batch(directions))
+            .map(|(s1, s2)| sum_supports(s1, s2))
+            .collect()
+    }
+}
+
+/// The support of a sum from the supports of the two operands.
+fn sum_supports<N, const D: usize>(
+    s1: Result<(N, SVector<N, D>), SetError>,
+    s2: Result<(N, SVector<N, D>), SetError>,
+) -> Result<(N, SVector<N, D>), SetError>
+where
+    N: RealField,
+{
+    // the sum is empty if either operand is, even if the other is unbounded
+    if matches!(s1, Err(SetError::Infeasible)) || matches!(s2, Err(SetError::Infeasible)) {
+        return Err(SetError::Infeasible);
+    }
+    let (d1, p1) = s1?;
+    let (d2, p2) = s2?;
+    Ok((d1 + d2, p1 + p2))
+}
+
+/// The Minkowski sum of an array of convex sets, the set of all sums of one point of each set.
+///
+/// The sum of no sets is the singleton at the origin.
+pub struct MinkowskiSumArray<N, const D: usize> {
+    /// The support functions of the summands.
+    sfs: Vec<SharedSet<N, D>>,
+}
+
+impl<N, const D: usize> MinkowskiSumArray<N, D> {
+    /// Create a new Minkowski sum of an array of sets.
+    pub fn new(sfs: Vec<SharedSet<N, D>>) -> MinkowskiSumArray<N, D> {
+        MinkowskiSumArray { sfs }
+    }
+
+    /// Add a set to the sum.
+    pub fn push(&mut self, sf: SharedSet<N, D>) {
+        self.sfs.push(sf);
+    }
+}
+
+impl<N, const D: usize> LazySet<N, D> for MinkowskiSumArray<N, D>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &SVector<N, D>) -> Result<(N, SVector<N, D>), SetError> {
+        // keep going past unbounded summands, since an empty one makes the whole sum empty
+        let mut sum = Ok((N::zero(), SVector::zeros()));
+        for sf in self.sfs.iter() {
+            sum = sum_supports(sum, sf.try_support(direction));
+        }
+        sum
+    }
+
+    fn try_support_batch(
+        &self,
+        directions: &DirectionMatrix<N, D>,
+    ) -> Vec<Result<(N, SVector<N, D>), SetError>> {
+        let zero = (0..directions.ncols())
+            .map(|_| Ok((N::zero(), SVector::zeros())))
+            .collect();
+        self.sfs.iter().fold(zero, |sums: Vec<_>, sf| {
+            sums.into_iter()
+                .zip(sf.try_support_batch(directions))
+                .map(|(sum, s)| sum_supports(sum, s))
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use nalgebra::Vector2;
+
+    use super::MinkowskiSumArray;
+    use crate::convex::{
+        DirectionMatrix, EmptySet, HalfSpace, Hyperrectangle, Hypersphere, LazySet, SharedSet,
+    };
+
+    #[test]
+    fn batch_matches_single_supports() {
+        let square: SharedSet<f64, 2> = Arc::new(Hyperrectangle::new(
+            Vector2::new(1.0, 0.0),
+            Vector2::new(1.0, 2.0),
+        ));
+        let disk: SharedSet<f64, 2> = Arc::new(Hypersphere::new(1.5, Vector2::new(-1.0, 1.0)));
+        let halfspace: SharedSet<f64, 2> = Arc::new(HalfSpace::new(Vector2::new(1.0, 1.0), 0.5));
+        let directions = DirectionMatrix::<f64, 2>::from_columns(&[
+            Vector2::new(1.0, 0.0),
+            Vector2::new(-1.0, 2.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(0.0, 0.0),
+        ]);
+        for sets in [
+            vec![],
+            vec![square.clone(), disk.clone()],
+            vec![square.clone(), halfspace.clone(), disk.clone()],
+            vec![square, Arc::new(EmptySet::new()), halfspace],
+        ] {
+            let set = MinkowskiSumArray::new(sets);
+            let batch = set.try_support_batch(&directions);
+            for (result, direction) in batch.into_iter().zip(directions.column_iter()) {
+                assert_eq!(result, set.try_support(&direction.into_owned()));
+            }
+        }
     }
 }
@@ -1,10 +1,18 @@
 /*!
  * Operations on geometric domains.
  */
+pub mod cached;
+pub mod exponential;
 pub mod hull;
+pub mod intersection;
 pub mod minkowski;
+pub mod product;
 pub mod transform;
 
-pub use hull::ConvexHull;
-pub use minkowski::MinkowskiSum;
-pub use transform::LinearTransformation;
+pub use cached::{CacheKey, Cached};
+pub use exponential::{DExponentialMap, ExpMethod, ExponentialMap, LinearOperator};
+pub use hull::{ConvexHull, ConvexHullArray};
+pub use intersection::Intersection;
+pub use minkowski::{MinkowskiSum, MinkowskiSumArray};
+pub use product::CartesianProduct;
+pub use transform::{AffineMap, DLinearMap, LinearMap, LinearTransformation, Translation};
@@ -0,0 +1,64 @@
+/*!
+ * Cartesian Product
+ */
+use nalgebra::{RealField, SVector};
+
+use crate::convex::{expect_support, LazySet, SharedSet};
+use crate::error::SetError;
+
+/// The Cartesian product of a set in dimension D1 and a set in dimension D2, a set in dimension D.
+///
+/// D must equal D1 + D2, which is checked at compile time when the product is created.
+pub struct CartesianProduct<N, const D1: usize, const D2: usize, const D: usize> {
+    /// The set of the first D1 coordinates.
+    lhs: SharedSet<N, D1>,
+    /// The set of the last D2 coordinates.
+    rhs: SharedSet<N, D2>,
+}
+
+impl<N, const D1: usize, const D2: usize, const D: usize> CartesianProduct<N, D1, D2, D> {
+    /// Fails to compile when the dimensions don't add up.
+    const DIMENSION_CHECK: () = assert!(D1 + D2 == D, "D must equal D1 + D2");
+
+    /// Create a new Cartesian product of two sets.
+    pub fn new(lhs: SharedSet<N, D1>, rhs: SharedSet<N, D2>) -> CartesianProduct<N, D1, D2, D> {
+        let () = Self::DIMENSION_CHECK;
+        CartesianProduct { lhs, rhs }
+    }
+}
+
+impl<N, const D1: usize, const D2: usize, const D: usize> LazySet<N, D>
+    for CartesianProduct<N, D1, D2, D>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
+        expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &SVector<N, D>) -> Result<(N, SVector<N, D>), SetError> {
+        // the support splits into the supports of the blocks of the direction
+        let s1 = self
+            .lhs
+            .try_support(&direction.fixed_rows::<D1>(0).into_owned());
+        let s2 = self
+            .rhs
+            .try_support(&direction.fixed_rows::<D2>(D1).into_owned());
+        // the product is empty if either operand is, even if the other is unbounded
+        if matches!(s1, Err(SetError::Infeasible)) || matches!(s2, Err(SetError::Infeasible)) {
+            return Err(SetError::Infeasible);
+        }
+        let (d1, p1) = s1?;
+        let (d2, p2) = s2?;
+
+        let mut point = SVector::<N, D>::zeros();
+        point.fixed_rows_mut::<D1>(0).copy_from(&p1);
+        point.fixed_rows_mut::<D2>(D1).copy_from(&p2);
+        Ok((d1 + d2, point))
+    }
+
+    fn is_member(&self, point: &SVector<N, D>) -> bool {
+        self.lhs.is_member(&point.fixed_rows::<D1>(0).into_owned())
+            && self.rhs.is_member(&point.fixed_rows::<D2>(D1).into_owned())
+    }
+}
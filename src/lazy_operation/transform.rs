@@ -1,34 +1,314 @@
 /*!
- * The linear transformation operation.
+ * The linear and affine map operations.
  */
-use nalgebra::{RealField, SMatrix, SVector};
+use nalgebra::{DMatrix, DVector, RealField, SMatrix, SVector};
 
-use crate::convex::LazySet;
+use crate::convex::{
+    d_expect_support, expect_support, DLazySet, DSharedSet, DirectionMatrix, LazySet, SharedSet,
+};
+use crate::error::SetError;
 
-/// A linear transformation of a set.
-pub struct LinearTransformation<N, const D: usize> {
-    /// The linear transformation matrix.
-    a_transform: SMatrix<N, D, D>,
+/// A linear map of a set in dimension D into dimension M.
+pub struct LinearMap<N, const M: usize, const D: usize> {
+    /// The linear map matrix.
+    a_transform: SMatrix<N, M, D>,
     /// The support function.
-    sf: Box<dyn LazySet<N, D>>,
+    sf: SharedSet<N, D>,
 }
 
-impl<N, const D: usize> LinearTransformation<N, D> {
-    /// Create a new linear transformation of a set.
+/// A linear transformation of a set, a linear map with a square matrix.
+pub type LinearTransformation<N, const D: usize> = LinearMap<N, D, D>;
+
+impl<N, const M: usize, const D: usize> LinearMap<N, M, D> {
+    /// Create a new linear map of a set.
+    pub fn new(a_transform: SMatrix<N, M, D>, sf: SharedSet<N, D>) -> LinearMap<N, M, D> {
+        LinearMap { a_transform, sf }
+    }
+}
+
+impl<N, const M: usize, const D: usize> LazySet<N, M> for LinearMap<N, M, D>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &SVector<N, M>) -> (N, SVector<N, M>) {
+        expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &SVector<N, M>) -> Result<(N, SVector<N, M>), SetError> {
+        let (d, p) = self
+            .sf
+            .try_support(&(self.a_transform.transpose() * direction))?;
+        Ok((d, self.a_transform * p))
+    }
+
+    fn try_support_batch(
+        &self,
+        directions: &DirectionMatrix<N, M>,
+    ) -> Vec<Result<(N, SVector<N, M>), SetError>> {
+        // map all directions with one matrix product
+        self.sf
+            .try_support_batch(&(self.a_transform.transpose() * directions))
+            .into_iter()
+            .map(|result| result.map(|(d, p)| (d, self.a_transform * p)))
+            .collect()
+    }
+}
+
+/// An affine map A X + b of a set in dimension D into dimension M.
+pub struct AffineMap<N, const M: usize, const D: usize> {
+    /// The linear map matrix.
+    a_transform: SMatrix<N, M, D>,
+    /// The offset b.
+    offset: SVector<N, M>,
+    /// The support function.
+    sf: SharedSet<N, D>,
+}
+
+impl<N, const M: usize, const D: usize> AffineMap<N, M, D> {
+    /// Create a new affine map of a set.
     pub fn new(
-        a_transform: SMatrix<N, D, D>,
-        sf: Box<dyn LazySet<N, D>>,
-    ) -> LinearTransformation<N, D> {
-        LinearTransformation { a_transform, sf }
+        a_transform: SMatrix<N, M, D>,
+        offset: SVector<N, M>,
+        sf: SharedSet<N, D>,
+    ) -> AffineMap<N, M, D> {
+        AffineMap {
+            a_transform,
+            offset,
+            sf,
+        }
+    }
+}
+
+impl<N, const M: usize, const D: usize> LazySet<N, M> for AffineMap<N, M, D>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &SVector<N, M>) -> (N, SVector<N, M>) {
+        expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &SVector<N, M>) -> Result<(N, SVector<N, M>), SetError> {
+        let (d, p) = self
+            .sf
+            .try_support(&(self.a_transform.transpose() * direction))?;
+        Ok((
+            d + direction.dot(&self.offset),
+            self.a_transform * p + self.offset,
+        ))
+    }
+}
+
+/// A translation X + v of a set.
+pub struct Translation<N, const D: usize> {
+    /// The translation vector v.
+    offset: SVector<N, D>,
+    /// The support function.
+    sf: SharedSet<N, D>,
+}
+
+impl<N, const D: usize> Translation<N, D> {
+    /// Create a new translation of a set.
+    pub fn new(offset: SVector<N, D>, sf: SharedSet<N, D>) -> Translation<N, D> {
+        Translation { offset, sf }
     }
 }
 
-impl<N, const D: usize> LazySet<N, D> for LinearTransformation<N, D>
+impl<N, const D: usize> LazySet<N, D> for Translation<N, D>
 where
     N: RealField,
 {
     fn support(&self, direction: &SVector<N, D>) -> (N, SVector<N, D>) {
-        let (d, p) = self.sf.support(&(self.a_transform.transpose() * direction));
-        (d, self.a_transform * p)
+        expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &SVector<N, D>) -> Result<(N, SVector<N, D>), SetError> {
+        let (d, p) = self.sf.try_support(direction)?;
+        Ok((d + direction.dot(&self.offset), p + self.offset))
+    }
+
+    fn constraints(&self) -> Option<(DMatrix<N>, DVector<N>)> {
+        // A (x - v) <= b becomes A x <= b + A v
+        let (a_transform, b) = self.sf.constraints()?;
+        let shift = &a_transform * DVector::from_column_slice(self.offset.as_slice());
+        Some((a_transform, b + shift))
+    }
+
+    fn is_member(&self, point: &SVector<N, D>) -> bool {
+        self.sf.is_member(&(point - self.offset))
+    }
+}
+
+/// A linear map of a dynamically sized set.
+pub struct DLinearMap<N> {
+    /// The linear map matrix.
+    a_transform: DMatrix<N>,
+    /// The support function.
+    sf: DSharedSet<N>,
+}
+
+impl<N> DLinearMap<N> {
+    /// Create a new linear map of a set.
+    pub fn new(a_transform: DMatrix<N>, sf: DSharedSet<N>) -> DLinearMap<N> {
+        DLinearMap { a_transform, sf }
+    }
+}
+
+impl<N> DLazySet<N> for DLinearMap<N>
+where
+    N: RealField,
+{
+    fn support(&self, direction: &DVector<N>) -> (N, DVector<N>) {
+        d_expect_support(self.try_support(direction), direction)
+    }
+
+    fn try_support(&self, direction: &DVector<N>) -> Result<(N, DVector<N>), SetError> {
+        if direction.len() != self.a_transform.nrows() {
+            return Err(SetError::DimensionMismatch {
+                expected: self.a_transform.nrows(),
+                found: direction.len(),
+            });
+        }
+
+        let (d, p) = self
+            .sf
+            .try_support(&(self.a_transform.transpose() * direction))?;
+        if p.len() != self.a_transform.ncols() {
+            return Err(SetError::DimensionMismatch {
+                expected: self.a_transform.ncols(),
+                found: p.len(),
+            });
+        }
+        Ok((d, &self.a_transform * p))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.sf.is_empty()
+    }
+
+    fn try_support_batch(&self, directions: &DMatrix<N>) -> Vec<Result<(N, DVector<N>), SetError>> {
+        if directions.nrows() != self.a_transform.nrows() {
+            let error = SetError::DimensionMismatch {
+                expected: self.a_transform.nrows(),
+                found: directions.nrows(),
+            };
+            return vec![Err(error); directions.ncols()];
+        }
+
+        // map all directions with one matrix product
+        self.sf
+            .try_support_batch(&self.a_transform.tr_mul(directions))
+            .into_iter()
+            .map(|result| {
+                let (d, p) = result?;
+                if p.len() != self.a_transform.ncols() {
+                    return Err(SetError::DimensionMismatch {
+                        expected: self.a_transform.ncols(),
+                        found: p.len(),
+                    });
+                }
+                Ok((d, &self.a_transform * p))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use nalgebra::{DMatrix, DVector, Matrix1x2, Matrix2, Vector1, Vector2};
+
+    use super::{AffineMap, DLinearMap, LinearMap, Translation};
+    use crate::convex::{DLazySet, DVPolytope, Hyperrectangle, LazySet};
+    use crate::error::SetError;
+
+    #[test]
+    fn projection_of_box() {
+        // x + y over [0, 2] x [1.5, 2.5] ranges over [1.5, 4.5]
+        let set = LinearMap::new(
+            Matrix1x2::new(1.0, 1.0),
+            Arc::new(Hyperrectangle::new(
+                Vector2::new(1.0, 2.0),
+                Vector2::new(1.0, 0.5),
+            )),
+        );
+        assert_eq!(set.support(&Vector1::new(1.0)), (4.5, Vector1::new(4.5)));
+        assert_eq!(set.support(&Vector1::new(-2.0)), (-3.0, Vector1::new(1.5)));
+    }
+
+    #[test]
+    fn dynamic_map_of_triangle() {
+        // embed the triangle into 3-D with the sum of the coordinates as third one
+        let set = DLinearMap::new(
+            DMatrix::from_row_slice(3, 2, &[1.0, 0.0, 0.0, 1.0, 1.0, 1.0]),
+            Arc::new(DVPolytope::new(vec![
+                DVector::from_vec(vec![0.0, 0.0]),
+                DVector::from_vec(vec![2.0, 0.0]),
+                DVector::from_vec(vec![0.0, 1.0]),
+            ])),
+        );
+        let (value, point) = set.support(&DVector::from_vec(vec![0.0, 0.0, 1.0]));
+        assert_eq!(value, 2.0);
+        assert_eq!(point, DVector::from_vec(vec![2.0, 0.0, 2.0]));
+        let (value, point) = set.support(&DVector::from_vec(vec![-1.0, 1.0, 0.0]));
+        assert_eq!(value, 1.0);
+        assert_eq!(point, DVector::from_vec(vec![0.0, 1.0, 1.0]));
+
+        assert_eq!(
+            set.try_support(&DVector::from_vec(vec![1.0, 0.0])),
+            Err(SetError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn rotated_and_shifted_box() {
+        // rotate [-1, 1] x [-2, 2] by a quarter turn and shift it by (1, 1)
+        let set = AffineMap::new(
+            Matrix2::new(0.0, -1.0, 1.0, 0.0),
+            Vector2::new(1.0, 1.0),
+            Arc::new(Hyperrectangle::new(
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 2.0),
+            )),
+        );
+        assert_eq!(
+            set.support(&Vector2::new(1.0, 0.0)),
+            (3.0, Vector2::new(3.0, 2.0))
+        );
+        assert_eq!(
+            set.support(&Vector2::new(0.0, -1.0)),
+            (0.0, Vector2::new(-1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn translated_box() {
+        let set = Translation::new(
+            Vector2::new(2.0, -1.0),
+            Arc::new(Hyperrectangle::new(
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 1.0),
+            )),
+        );
+        assert_eq!(
+            set.support(&Vector2::new(1.0, 1.0)),
+            (3.0, Vector2::new(3.0, 0.0))
+        );
+
+        // x <= 1, y <= 1, -x <= 1 and -y <= 1 shifted to [1, 3] x [-2, 0]
+        let (a_transform, b) = set.constraints().unwrap();
+        assert_eq!(
+            a_transform,
+            DMatrix::from_row_slice(4, 2, &[1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0])
+        );
+        assert_eq!(b, DVector::from_vec(vec![3.0, 0.0, -1.0, 2.0]));
+
+        assert!(set.is_member(&Vector2::new(3.0, -2.0)));
+        assert!(set.is_member(&Vector2::new(1.5, -0.5)));
+        assert!(!set.is_member(&Vector2::new(0.5, -0.5)));
+        assert!(!set.is_member(&Vector2::new(2.0, 0.5)));
     }
 }
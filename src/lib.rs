@@ -10,6 +10,11 @@
 #![warn(unused_imports)]
 #![allow(missing_copy_implementations)]
 
+pub mod check;
 pub mod convex;
+pub mod error;
 pub mod lazy_operation;
+pub mod lp;
 pub mod overapproximate;
+pub mod parallel;
+pub mod volume;
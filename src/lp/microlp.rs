@@ -0,0 +1,62 @@
+/*!
+ * The microlp linear programming backend.
+ */
+use microlp::{ComparisonOp, Error, OptimizationDirection, Problem, Variable};
+use nalgebra::{DMatrix, DVector};
+
+use super::LpSolver;
+use crate::error::SetError;
+
+/// Solves linear programs with the pure-Rust microlp crate, a maintained fork of minilp.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MicroLp;
+
+impl LpSolver for MicroLp {
+    fn maximize(
+        &self,
+        objective: &DVector<f64>,
+        a_transform: &DMatrix<f64>,
+        upper_bounds: &DVector<f64>,
+    ) -> Result<(f64, DVector<f64>), SetError> {
+        let mut problem = Problem::new(OptimizationDirection::Maximize);
+
+        // make a variable for every column in A, split into nonnegative parts x = x+ - x-
+        // since microlp, like minilp, can stall on free variables
+        let mut vars = Vec::new();
+        for c in objective.iter() {
+            vars.push((
+                problem.add_var(*c, (0.0, f64::INFINITY)),
+                problem.add_var(-*c, (0.0, f64::INFINITY)),
+            ));
+        }
+
+        // add constraints from A and b
+        for i in 0..a_transform.nrows() {
+            let mut row = Vec::<(Variable, f64)>::new();
+            for (j, (pos, neg)) in vars.iter().enumerate() {
+                let a = a_transform[(i, j)];
+                if a != 0.0 {
+                    row.push((*pos, a));
+                    row.push((*neg, -a));
+                }
+            }
+            problem.add_constraint(row.as_slice(), ComparisonOp::Le, upper_bounds[i]);
+        }
+
+        let solution = problem.solve().map_err(|e| match e {
+            Error::Infeasible => SetError::Infeasible,
+            Error::Unbounded => SetError::Unbounded,
+            Error::InternalError(_) => SetError::SolverFailure,
+        })?;
+        if !solution.objective().is_finite() {
+            return Err(SetError::Unbounded);
+        }
+
+        let point = DVector::from_iterator(
+            vars.len(),
+            vars.iter()
+                .map(|(pos, neg)| solution[*pos] - solution[*neg]),
+        );
+        Ok((solution.objective(), point))
+    }
+}
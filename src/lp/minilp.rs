@@ -0,0 +1,62 @@
+/*!
+ * The minilp linear programming backend.
+ */
+use minilp::{ComparisonOp, Error, OptimizationDirection, Problem, Variable};
+use nalgebra::{DMatrix, DVector};
+
+use super::LpSolver;
+use crate::error::SetError;
+
+/// Solves linear programs with the pure-Rust minilp crate.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MiniLp;
+
+impl LpSolver for MiniLp {
+    fn maximize(
+        &self,
+        objective: &DVector<f64>,
+        a_transform: &DMatrix<f64>,
+        upper_bounds: &DVector<f64>,
+    ) -> Result<(f64, DVector<f64>), SetError> {
+        let mut problem = Problem::new(OptimizationDirection::Maximize);
+
+        // make a variable for every column in A, split into nonnegative parts x = x+ - x-
+        // since minilp mishandles free variables when the optimal face is unbounded
+        let mut vars = Vec::new();
+        for c in objective.iter() {
+            vars.push((
+                problem.add_var(*c, (0.0, f64::INFINITY)),
+                problem.add_var(-*c, (0.0, f64::INFINITY)),
+            ));
+        }
+
+        // add constraints from A and b, leaving out zeros that minilp may turn into NaNs
+        for i in 0..a_transform.nrows() {
+            let mut row = Vec::<(Variable, f64)>::new();
+            for (j, (pos, neg)) in vars.iter().enumerate() {
+                let a = a_transform[(i, j)];
+                if a != 0.0 {
+                    row.push((*pos, a));
+                    row.push((*neg, -a));
+                }
+            }
+            problem.add_constraint(row.as_slice(), ComparisonOp::Le, upper_bounds[i]);
+        }
+
+        let solution = problem.solve().map_err(|e| match e {
+            Error::Infeasible => SetError::Infeasible,
+            Error::Unbounded => SetError::Unbounded,
+        })?;
+        // minilp may also report unboundedness through an infinite objective
+        if !solution.objective().is_finite() {
+            return Err(SetError::Unbounded);
+        }
+
+        let point = DVector::from_iterator(
+            vars.len(),
+            vars.iter()
+                .map(|(pos, neg)| solution[*pos] - solution[*neg]),
+        );
+        Ok((solution.objective(), point))
+    }
+}
@@ -0,0 +1,121 @@
+/*!
+ * Linear programming backends used by the polyhedral sets.
+ */
+#[cfg(feature = "microlp")]
+pub mod microlp;
+pub mod minilp;
+pub mod warm_start;
+
+use nalgebra::{DMatrix, DVector, RealField};
+use num_traits::{FromPrimitive, ToPrimitive};
+
+use crate::convex::within_bound;
+use crate::error::SetError;
+
+#[cfg(feature = "microlp")]
+pub use self::microlp::MicroLp;
+pub use self::minilp::MiniLp;
+pub use self::warm_start::WarmStart;
+
+/// A solver for linear programs max c^T x subject to Ax <= b over free variables x.
+///
+/// Solvers are shared between threads when LPs are solved in parallel.
+pub trait LpSolver: Sync {
+    /// Returns the optimal value and an optimal point.
+    ///
+    /// Reports [`SetError::Infeasible`] if Ax <= b has no solution and [`SetError::Unbounded`] if
+    /// the objective is unbounded from above.
+    fn maximize(
+        &self,
+        objective: &DVector<f64>,
+        a_transform: &DMatrix<f64>,
+        upper_bounds: &DVector<f64>,
+    ) -> Result<(f64, DVector<f64>), SetError>;
+
+    /// Returns [`LpSolver::maximize`] for each column of `objectives` over the same constraints.
+    /// Every LP is solved from scratch, see [`WarmStart`] for reusing the optimal vertex of the
+    /// previous LP. With the `parallel` feature the LPs are solved on multiple threads.
+    fn maximize_batch(
+        &self,
+        objectives: &DMatrix<f64>,
+        a_transform: &DMatrix<f64>,
+        upper_bounds: &DVector<f64>,
+    ) -> Vec<Result<(f64, DVector<f64>), SetError>> {
+        let solve = |j: usize| {
+            self.maximize(
+                &objectives.column(j).into_owned(),
+                a_transform,
+                upper_bounds,
+            )
+        };
+        #[cfg(feature = "parallel")]
+        let results = {
+            use rayon::prelude::*;
+            (0..objectives.ncols()).into_par_iter().map(solve).collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let results = (0..objectives.ncols()).map(solve).collect();
+        results
+    }
+}
+
+/// Converts scalars in column major order to f64 for a solver.
+pub(crate) fn to_f64_vec<'a, N, I>(values: I) -> Result<Vec<f64>, SetError>
+where
+    N: ToPrimitive + 'a,
+    I: IntoIterator<Item = &'a N>,
+{
+    values
+        .into_iter()
+        .map(|x| x.to_f64().ok_or(SetError::NumericConversion))
+        .collect()
+}
+
+/// Converts a solver value back to the scalar type.
+pub(crate) fn from_f64<N: FromPrimitive>(x: f64) -> Result<N, SetError> {
+    N::from_f64(x).ok_or(SetError::NumericConversion)
+}
+
+/// determines for a polyhedral set Ax <= b, determine if a new constraint c^T x <= d is redundant
+///
+/// The faces are the rows of A, each with as many entries as the new face.
+pub(crate) fn is_constraint_redundant<N, S>(
+    solver: &S,
+    faces: &[&[N]],
+    upper_bounds: &[N],
+    new_face: &[N],
+    new_upper_bound: N,
+) -> Result<bool, SetError>
+where
+    N: RealField + ToPrimitive,
+    S: LpSolver,
+{
+    // let A = faces, b = upper_bounds, c = new_face, d = new_upper_bound
+    // solve max c^T x st
+    // Ax <= b
+    // c^T x <= d + 1
+
+    // stack the faces and the new face as rows of the constraint matrix
+    let mut a_transform = DMatrix::<f64>::zeros(faces.len() + 1, new_face.len());
+    for (i, face) in faces.iter().chain(std::iter::once(&new_face)).enumerate() {
+        for (j, a) in to_f64_vec(face.iter())?.into_iter().enumerate() {
+            a_transform[(i, j)] = a;
+        }
+    }
+    let mut b = to_f64_vec(upper_bounds.iter())?;
+    b.push(
+        new_upper_bound
+            .to_f64()
+            .ok_or(SetError::NumericConversion)?
+            + 1.0,
+    );
+
+    let (objective, _) = solver.maximize(
+        &DVector::from_vec(to_f64_vec(new_face.iter())?),
+        &a_transform,
+        &DVector::from_vec(b),
+    )?;
+
+    // rounding in the LP must not keep redundant or nearly duplicate constraints
+    Ok(within_bound(from_f64::<N>(objective)?, new_upper_bound))
+}
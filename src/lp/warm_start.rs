@@ -0,0 +1,276 @@
+/*!
+ * Warm starts for LPs over the same constraints.
+ */
+use std::ops::Range;
+
+use nalgebra::{DMatrix, DVector};
+
+use super::LpSolver;
+use crate::error::SetError;
+
+/// The maximum number of pivots of a warm started LP before it is left to the solver.
+const MAX_PIVOTS: usize = 256;
+
+/// The relative tolerance for active constraints, pivots and multipliers.
+const TOLERANCE: f64 = 1e-9;
+
+/// Solves batches of LPs over the same constraints with a solver, warm starting each LP from the
+/// optimal vertex of the previous one.
+///
+/// The inner solver is only called for the first LP of a batch and where no vertex is known, e.g.
+/// since the previous LP was unbounded or its optimal point isn't a vertex. The other LPs are
+/// solved by primal simplex pivots from the previous vertex, which saves setting up the LP when the
+/// objectives are close, as for neighbouring template directions. Single LPs go to the inner
+/// solver. With the `parallel` feature the columns of a batch are split into one chain of warm
+/// starts per thread.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct WarmStart<S>(pub S);
+
+impl<S: LpSolver> LpSolver for WarmStart<S> {
+    fn maximize(
+        &self,
+        objective: &DVector<f64>,
+        a_transform: &DMatrix<f64>,
+        upper_bounds: &DVector<f64>,
+    ) -> Result<(f64, DVector<f64>), SetError> {
+        self.0.maximize(objective, a_transform, upper_bounds)
+    }
+
+    fn maximize_batch(
+        &self,
+        objectives: &DMatrix<f64>,
+        a_transform: &DMatrix<f64>,
+        upper_bounds: &DVector<f64>,
+    ) -> Vec<Result<(f64, DVector<f64>), SetError>> {
+        let solve = |columns: Range<usize>| {
+            let mut vertex: Option<Vertex> = None;
+            columns
+                .map(|j| {
+                    let objective = objectives.column(j).into_owned();
+                    if let Some(result) = vertex
+                        .as_mut()
+                        .and_then(|v| v.maximize(&objective, a_transform, upper_bounds))
+                    {
+                        return result;
+                    }
+                    let result = self.0.maximize(&objective, a_transform, upper_bounds);
+                    vertex = result
+                        .as_ref()
+                        .ok()
+                        .and_then(|(_, point)| Vertex::find(a_transform, upper_bounds, point));
+                    result
+                })
+                .collect::<Vec<_>>()
+        };
+        #[cfg(feature = "parallel")]
+        let results = {
+            use rayon::prelude::*;
+            let count = objectives.ncols();
+            let chunk = count.div_ceil(rayon::current_num_threads()).max(1);
+            (0..count)
+                .step_by(chunk)
+                .collect::<Vec<_>>()
+                .into_par_iter()
+                .map(|start| solve(start..count.min(start + chunk)))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .flatten()
+                .collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let results = solve(0..objectives.ncols());
+        results
+    }
+}
+
+/// A vertex of Ax <= b given by the rows of A that are active at it.
+///
+/// Starting from the vertex of a previous LP, [`Vertex::maximize`] runs the primal simplex method
+/// on the active rows: while a multiplier of the objective is negative, its row is released and the
+/// vertex moves along the edge that leaves it until another row becomes active. The rows are
+/// exchanged by Bland's rule, so the pivots don't cycle.
+#[derive(Clone, Debug)]
+struct Vertex {
+    /// The indices of the active rows, as many as there are columns in A.
+    rows: Vec<usize>,
+}
+
+impl Vertex {
+    /// The vertex at a point of Ax <= b, or `None` if the point isn't a vertex, for example since
+    /// the set contains a line.
+    fn find(
+        a_transform: &DMatrix<f64>,
+        upper_bounds: &DVector<f64>,
+        point: &DVector<f64>,
+    ) -> Option<Vertex> {
+        let dimension = a_transform.ncols();
+        let residuals = upper_bounds - a_transform * point;
+        // pick linearly independent active rows by Gram-Schmidt
+        let mut basis: Vec<DVector<f64>> = Vec::with_capacity(dimension);
+        let mut rows = Vec::with_capacity(dimension);
+        for i in 0..a_transform.nrows() {
+            if rows.len() == dimension {
+                break;
+            }
+            let row = a_transform.row(i).transpose();
+            if residuals[i].abs() > TOLERANCE.sqrt() * (1.0 + upper_bounds[i].abs()) {
+                continue;
+            }
+            let orthogonal = basis.iter().fold(row.clone(), |r, q| &r - q * q.dot(&row));
+            if orthogonal.norm() > TOLERANCE * (1.0 + row.norm()) {
+                basis.push(orthogonal.normalize());
+                rows.push(i);
+            }
+        }
+        (rows.len() == dimension).then_some(Vertex { rows })
+    }
+
+    /// Maximizes c^T x subject to Ax <= b starting from the vertex, which moves to the optimal
+    /// vertex.
+    ///
+    /// Returns `None` if the pivots run into numerical trouble or don't finish, in which case the
+    /// LP is left to the solver.
+    fn maximize(
+        &mut self,
+        objective: &DVector<f64>,
+        a_transform: &DMatrix<f64>,
+        upper_bounds: &DVector<f64>,
+    ) -> Option<Result<(f64, DVector<f64>), SetError>> {
+        let scale = objective.amax();
+        for _ in 0..MAX_PIVOTS {
+            let active = DMatrix::from_fn(self.rows.len(), a_transform.ncols(), |i, j| {
+                a_transform[(self.rows[i], j)]
+            });
+            let lu = active.clone().lu();
+            let point = lu.solve(&DVector::from_fn(self.rows.len(), |i, _| {
+                upper_bounds[self.rows[i]]
+            }))?;
+            let multipliers = active.transpose().lu().solve(objective)?;
+            // an ill-conditioned basis may land outside the set
+            let residuals = upper_bounds - a_transform * &point;
+            if (0..residuals.len())
+                .any(|i| residuals[i] < -TOLERANCE.sqrt() * (1.0 + upper_bounds[i].abs()))
+            {
+                return None;
+            }
+
+            // Bland's rule: release the lowest row with a negative multiplier
+            let Some(leaving) = (0..self.rows.len())
+                .filter(|&k| multipliers[k] < -TOLERANCE * (1.0 + scale))
+                .min_by_key(|&k| self.rows[k])
+            else {
+                return Some(Ok((objective.dot(&point), point)));
+            };
+
+            // the edge keeps the other rows active and leaves the released one
+            let mut unit = DVector::zeros(self.rows.len());
+            unit[leaving] = -1.0;
+            let edge = lu.solve(&unit)?;
+            let mut step: Option<(f64, usize)> = None;
+            for i in 0..a_transform.nrows() {
+                let rate = a_transform.row(i).dot(&edge.transpose());
+                if self.rows.contains(&i) || rate <= TOLERANCE * (1.0 + edge.amax()) {
+                    continue;
+                }
+                let length = ((upper_bounds[i] - a_transform.row(i).dot(&point.transpose()))
+                    / rate)
+                    .max(0.0);
+                // the lowest row among the nearest ones enters
+                if step.map_or(true, |(shortest, _)| length < shortest) {
+                    step = Some((length, i));
+                }
+            }
+            match step {
+                Some((_, entering)) => self.rows[leaving] = entering,
+                None => return Some(Err(SetError::Unbounded)),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{DMatrix, DVector};
+
+    use super::WarmStart;
+    use crate::error::SetError;
+    use crate::lp::{LpSolver, MiniLp};
+
+    /// The directions (cos, sin) of `count` angles around the circle.
+    fn objectives(count: usize) -> DMatrix<f64> {
+        DMatrix::from_fn(2, count, |i, j| {
+            let angle = std::f64::consts::TAU * j as f64 / count as f64;
+            if i == 0 {
+                angle.cos()
+            } else {
+                angle.sin()
+            }
+        })
+    }
+
+    /// Checks that the warm started batch agrees with solving each LP from scratch.
+    fn assert_batch_agrees(a_transform: &[f64], upper_bounds: &[f64]) {
+        let rows = upper_bounds.len();
+        let a_transform = DMatrix::from_row_slice(rows, 2, a_transform);
+        let upper_bounds = DVector::from_column_slice(upper_bounds);
+        let objectives = objectives(64);
+        let batch = WarmStart(MiniLp).maximize_batch(&objectives, &a_transform, &upper_bounds);
+        for (j, result) in batch.into_iter().enumerate() {
+            let single = MiniLp.maximize(
+                &objectives.column(j).into_owned(),
+                &a_transform,
+                &upper_bounds,
+            );
+            match (result, single) {
+                (Ok((value, point)), Ok((expected, _))) => {
+                    let scale = 1.0 + expected.abs();
+                    assert!((value - expected).abs() <= 1e-6 * scale, "{}: {}", j, value);
+                    let residuals = &upper_bounds - &a_transform * &point;
+                    assert!(residuals.min() >= -1e-6 * scale, "{}: {}", j, point);
+                }
+                (result, single) => assert_eq!(result.err(), single.err(), "{}", j),
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_vertex() {
+        // three rows meet at (1, 1)
+        assert_batch_agrees(
+            &[1.0, 0.0, 0.0, 1.0, 1.0, 1.0, -1.0, 0.0, 0.0, -1.0],
+            &[1.0, 1.0, 2.0, 1.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn duplicate_rows() {
+        assert_batch_agrees(
+            &[1.0, 0.0, 1.0, 0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, 0.0, 1.0],
+            &[1.0, 1.0, 1.0, 1.0, 1.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn unbounded_wedge() {
+        assert_batch_agrees(&[1.0, 1.0, 1.0, -1.0], &[0.0, 0.0]);
+    }
+
+    #[test]
+    fn empty_polytope() {
+        let a_transform = DMatrix::from_row_slice(2, 2, &[1.0, 0.0, -1.0, 0.0]);
+        let upper_bounds = DVector::from_column_slice(&[-1.0, -1.0]);
+        for result in WarmStart(MiniLp).maximize_batch(&objectives(8), &a_transform, &upper_bounds)
+        {
+            assert_eq!(result.err(), Some(SetError::Infeasible));
+        }
+    }
+
+    #[test]
+    fn ill_scaled_box() {
+        assert_batch_agrees(
+            &[1e6, 0.0, 0.0, 1e-6, -1e6, 0.0, 0.0, -1e-6],
+            &[1e6, 1e-6, 1e6, 1e-6],
+        );
+    }
+}
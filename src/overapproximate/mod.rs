@@ -1,99 +1,152 @@
 /*!
  * overapproximation algorithm for convex sets.
  */
-use minilp::{ComparisonOp, OptimizationDirection, Problem, Variable};
-use nalgebra::{DMatrix, DVector, RealField, SVector};
+use std::cmp::Ordering;
+
+use nalgebra::{Const, DMatrix, DVector, Dynamic, RealField, SVector};
 use num_traits::{FromPrimitive, ToPrimitive, Zero};
 use rand::{distributions::uniform::SampleUniform, Rng};
 
-use crate::convex::{DHalfspacePolytope, LazySet};
+use crate::convex::{box_directions, DHalfspacePolytope, DirectionMatrix, LazySet};
+use crate::error::SetError;
+use crate::lp::{is_constraint_redundant, LpSolver, MiniLp};
+use crate::parallel;
+
+/// The directions along which a set is overapproximated.
+#[derive(Clone)]
+pub enum Directions<N, const D: usize> {
+    /// The box directions ±e_i, giving the bounding box of the set.
+    Box,
+    /// The octagonal directions ±e_i and ±e_i ± e_j.
+    Octagonal,
+    /// A user supplied template of directions.
+    Template(Vec<SVector<N, D>>),
+}
 
-/// determines for a polyhedral set Ax <= b, determine if a new constraint c^T x <= d is redundant
-fn is_constraint_redundant<N, const D: usize>(
-    faces: &Vec<SVector<N, D>>,
-    upper_bounds: &Vec<N>,
-    new_face: SVector<N, D>,
-    new_upper_bound: N,
-) -> bool
+impl<N, const D: usize> Directions<N, D>
 where
-    N: RealField + ToPrimitive,
+    N: RealField + Copy,
 {
-    // let A = faces, b = upper_bounds, c = new_face, d = new_upper_bound
-    // solve max c^T x st
-    // Ax <= b
-    // c^T x <= d
-
-    let mut problem = Problem::new(OptimizationDirection::Maximize);
-
-    // make variables and multiply be new_face
-    let mut vars = Vec::new();
-    for i in 0..new_face.len() {
-        vars.push(problem.add_var(
-            new_face[i].to_f64().unwrap(),
-            (f64::NEG_INFINITY, f64::INFINITY),
-        ));
-    }
-
-    // add constraints from faces and upper_bounds
-    for i in 0..faces.len() {
-        let mut row = Vec::<(Variable, f64)>::new();
-        for j in 0..faces[i].len() {
-            row.push((vars[j], faces[i][j].to_f64().unwrap()));
+    /// Sample a template of random unit directions from the given random number generator.
+    pub fn random<R: Rng + ?Sized>(num_samples: usize, rng: &mut R) -> Directions<N, D>
+    where
+        N: SampleUniform,
+    {
+        let mut vectors = Vec::new();
+        for _ in 0..num_samples {
+            // create a random vector
+            let mut v = SVector::<N, D>::zero();
+            for i in 0..D {
+                v[i] = rng.gen_range(-N::one()..N::one());
+            }
+            vectors.push(v.normalize());
         }
-        // convert row into list
-        problem.add_constraint(
-            row.as_slice(),
-            ComparisonOp::Le,
-            upper_bounds[i].to_f64().unwrap(),
-        );
+        Directions::Template(vectors)
     }
 
-    // add new_face * x <= new_upper_bound + 1
-    let mut row = Vec::<(Variable, f64)>::new();
-    for i in 0..new_face.len() {
-        row.push((vars[i], new_face[i].to_f64().unwrap()));
+    /// The list of direction vectors.
+    pub fn vectors(&self) -> Vec<SVector<N, D>> {
+        match self {
+            Directions::Box => box_vectors(),
+            Directions::Octagonal => {
+                let mut vectors = box_vectors();
+                let signs = [N::one(), -N::one()];
+                for i in 0..D {
+                    for j in (i + 1)..D {
+                        for si in signs.iter() {
+                            for sj in signs.iter() {
+                                let mut v = SVector::<N, D>::zero();
+                                v[i] = *si;
+                                v[j] = *sj;
+                                vectors.push(v);
+                            }
+                        }
+                    }
+                }
+                vectors
+            }
+            Directions::Template(vectors) => vectors.clone(),
+        }
     }
-    problem.add_constraint(
-        row.as_slice(),
-        ComparisonOp::Le,
-        new_upper_bound.to_f64().unwrap() + 1.0,
-    );
-
-    let solution = problem.solve().unwrap();
-    let objective = N::from_f64(solution.objective()).unwrap();
+}
 
-    return objective <= new_upper_bound;
+/// The directions ±e_i.
+fn box_vectors<N, const D: usize>() -> Vec<SVector<N, D>>
+where
+    N: RealField + Copy,
+{
+    box_directions::<N>(D)
+        .column_iter()
+        .map(|e| SVector::<N, D>::from_iterator(e.iter().copied()))
+        .collect()
 }
 
-/// overapproximate a convex set with a dynamically allocated H polytope
+/// overapproximate a convex set with a dynamically allocated H polytope along random directions
+///
+/// Directions in which the set is unbounded are skipped, while an empty set is reported as
+/// [`SetError::Infeasible`]. The directions are drawn from the thread local random number
+/// generator; use [`overapproximate_with`] and [`Directions::random`] for reproducible results.
 pub fn overapproximate<N, const D: usize>(
-    convex_set: &dyn LazySet<N, D>,
+    convex_set: &(dyn LazySet<N, D> + Sync),
     num_samples: usize,
-) -> DHalfspacePolytope<N>
+) -> Result<DHalfspacePolytope<N>, SetError>
 where
     N: RealField + Copy + FromPrimitive + ToPrimitive + SampleUniform,
 {
-    // generate random unit vectors pointing uniformly in the unit sphere
-    let mut rng = rand::thread_rng();
-    let mut unit_vectors = Vec::new();
+    let directions = Directions::random(num_samples, &mut rand::thread_rng());
+    overapproximate_with(convex_set, &directions)
+}
 
-    // compute the support function of the convex set for each unit vector
-    let mut bs = Vec::new();
-    for _ in 0..num_samples {
-        // create a random vector
-        let mut v = SVector::<N, D>::zero();
-        for i in 0..D {
-            v[i] = rng.gen_range(N::from_f64(-1.0).unwrap()..N::from_f64(1.0).unwrap());
-        }
-        v = v.normalize();
+/// overapproximate a convex set with a dynamically allocated H polytope along the given directions
+///
+/// Constraints that are redundant with respect to the previously added ones are left out. With the
+/// `parallel` feature the support functions are evaluated on multiple threads.
+pub fn overapproximate_with<N, const D: usize>(
+    convex_set: &(dyn LazySet<N, D> + Sync),
+    directions: &Directions<N, D>,
+) -> Result<DHalfspacePolytope<N>, SetError>
+where
+    N: RealField + Copy + FromPrimitive + ToPrimitive,
+{
+    overapproximate_with_solver(convex_set, directions, MiniLp)
+}
 
-        // compute sigma and rho
-        let (_, sigma) = convex_set.support(&v);
+/// overapproximate a convex set along the given directions like [`overapproximate_with`], solving
+/// the redundancy checks with the given LP solver, which the returned polytope uses as well
+pub fn overapproximate_with_solver<N, S, const D: usize>(
+    convex_set: &(dyn LazySet<N, D> + Sync),
+    directions: &Directions<N, D>,
+    solver: S,
+) -> Result<DHalfspacePolytope<N, S>, SetError>
+where
+    N: RealField + Copy + FromPrimitive + ToPrimitive,
+    S: LpSolver,
+{
+    let mut unit_vectors = Vec::new();
 
-        // get new upper bound
-        let b = sigma.dot(&v);
+    // compute the support function of the convex set for all directions at once
+    let vectors = directions.vectors();
+    let direction_matrix = DirectionMatrix::<N, D>::from_fn_generic(
+        Const::<D>,
+        Dynamic::new(vectors.len()),
+        |i, j| vectors[j][i],
+    );
+    let supports = parallel::try_support_batch(convex_set, &direction_matrix);
+
+    let mut bs = Vec::new();
+    for (v, support) in vectors.into_iter().zip(supports) {
+        // rho is the new upper bound; unbounded directions give no constraint
+        let b = match support {
+            Ok((b, _)) => b,
+            Err(SetError::Unbounded) => continue,
+            Err(e) => return Err(e),
+        };
 
-        if !is_constraint_redundant::<N, D>(&unit_vectors, &bs, v, b) {
+        let faces: Vec<&[N]> = unit_vectors
+            .iter()
+            .map(|u: &SVector<N, D>| u.as_slice())
+            .collect();
+        if !is_constraint_redundant(&solver, &faces, &bs, v.as_slice(), b)? {
             bs.push(b);
             unit_vectors.push(v);
         }
@@ -102,8 +155,8 @@ where
     // flatten unit_vectors in column major order
     let mut unit_vectors_cm = Vec::new();
     for i in 0..D {
-        for j in 0..unit_vectors.len() {
-            unit_vectors_cm.push(unit_vectors[j][i]);
+        for unit_vector in unit_vectors.iter() {
+            unit_vectors_cm.push(unit_vector[i]);
         }
     }
 
@@ -111,5 +164,119 @@ where
     let b = DVector::<N>::from_vec(bs);
 
     // create a dynamic polytope with vn and bs
-    DHalfspacePolytope::<N>::new(a_transform, b)
+    Ok(DHalfspacePolytope::<N>::new(a_transform, b).with_solver(solver))
+}
+
+/// The maximum number of directions of [`overapproximate_epsilon`].
+pub const MAX_EPSILON_DIRECTIONS: usize = 1 << 20;
+
+/// A direction together with the support value and support point of a set in that direction.
+type SupportSample<N> = (SVector<N, 2>, N, SVector<N, 2>);
+
+/// overapproximate a planar convex set with a polygon within Hausdorff distance `tol`
+///
+/// Starting from the box directions, the directions are refined adaptively: for two neighbouring
+/// directions the outer approximation has a corner where their constraints meet, while the inner
+/// approximation has an edge between their support points. Whenever the corner is farther than
+/// `tol` from the edge, the normal of the edge is added as a new direction. A pair isn't refined
+/// further once the new support point coincides with one of theirs or the new direction is parallel
+/// to one of theirs, as the edge then lies on the boundary up to rounding. The constraints of the
+/// returned polytope are in counter-clockwise order.
+///
+/// Reports [`SetError::InvalidTolerance`] unless `tol` is positive and [`SetError::NotConverged`]
+/// if more than [`MAX_EPSILON_DIRECTIONS`] directions would be needed, which happens for tolerances
+/// near the rounding error of the support function, e.g. for sets far from the origin.
+pub fn overapproximate_epsilon<N>(
+    convex_set: &dyn LazySet<N, 2>,
+    tol: N,
+) -> Result<DHalfspacePolytope<N>, SetError>
+where
+    N: RealField + Copy,
+{
+    if tol.partial_cmp(&N::zero()) != Some(Ordering::Greater) {
+        return Err(SetError::InvalidTolerance);
+    }
+
+    let sample = |d: SVector<N, 2>| -> Result<SupportSample<N>, SetError> {
+        let (rho, p) = convex_set.try_support(&d)?;
+        Ok((d, rho, p))
+    };
+    let (one, zero) = (N::one(), N::zero());
+    let initial = [
+        sample(SVector::<N, 2>::new(one, zero))?,
+        sample(SVector::<N, 2>::new(zero, one))?,
+        sample(SVector::<N, 2>::new(-one, zero))?,
+        sample(SVector::<N, 2>::new(zero, -one))?,
+    ];
+    let size =
+        SVector::<N, 2>::new(initial[0].1 + initial[2].1, initial[1].1 + initial[3].1).norm();
+    // support points closer than this relative to the size of the set are considered equal
+    let resolution = N::default_epsilon().sqrt() * size;
+
+    // refine between each pair of neighbouring box directions
+    let mut samples = Vec::new();
+    for k in 0..initial.len() {
+        let mut left = initial[k];
+        samples.push(left);
+        let mut stack = vec![initial[(k + 1) % initial.len()]];
+        while let Some(right) = stack.last().copied() {
+            if approximation_error(&left, &right) > tol {
+                // the outward normal of the inner edge from left to right
+                let edge = right.2 - left.2;
+                let refined = sample(SVector::<N, 2>::new(edge[1], -edge[0]))?;
+                if !coincides(&refined, &left, resolution)
+                    && !coincides(&refined, &right, resolution)
+                {
+                    if samples.len() + stack.len() >= MAX_EPSILON_DIRECTIONS {
+                        return Err(SetError::NotConverged);
+                    }
+                    stack.push(refined);
+                    continue;
+                }
+            }
+            let _ = stack.pop();
+            if !stack.is_empty() {
+                samples.push(right);
+            }
+            left = right;
+        }
+    }
+
+    let a_transform = DMatrix::<N>::from_fn(samples.len(), 2, |i, j| samples[i].0[j]);
+    let b = DVector::<N>::from_iterator(samples.len(), samples.iter().map(|s| s.1));
+    Ok(DHalfspacePolytope::<N>::new(a_transform, b))
+}
+
+/// Returns true if two samples have the same support point up to `resolution` or parallel
+/// directions, so that refining between them makes no progress.
+fn coincides<N>(lhs: &SupportSample<N>, rhs: &SupportSample<N>, resolution: N) -> bool
+where
+    N: RealField + Copy,
+{
+    let ((d1, _, p1), (d2, _, p2)) = (lhs, rhs);
+    let det = d1[0] * d2[1] - d1[1] * d2[0];
+    (p1 - p2).norm() <= resolution || det.abs() <= N::default_epsilon() * d1.norm() * d2.norm()
+}
+
+/// The distance between the outer corner and the inner edge of two neighbouring samples.
+fn approximation_error<N>(left: &SupportSample<N>, right: &SupportSample<N>) -> N
+where
+    N: RealField + Copy,
+{
+    let ((d1, rho1, p1), (d2, rho2, p2)) = (left, right);
+    let edge = p2 - p1;
+    let det = d1[0] * d2[1] - d1[1] * d2[0];
+    if edge.norm() <= N::default_epsilon() || det.abs() <= N::default_epsilon() {
+        return N::zero();
+    }
+
+    // intersect the lines d1^T x = rho1 and d2^T x = rho2
+    let corner = SVector::<N, 2>::new(
+        (*rho1 * d2[1] - *rho2 * d1[1]) / det,
+        (d1[0] * *rho2 - d2[0] * *rho1) / det,
+    );
+
+    // distance from the corner to the edge p1 p2
+    let t = ((corner - p1).dot(&edge) / edge.norm_squared()).clamp(N::zero(), N::one());
+    (corner - (p1 + edge * t)).norm()
 }
@@ -0,0 +1,71 @@
+/*!
+ * Support function evaluations over many directions.
+ *
+ * With the `parallel` feature the directions are split into one chunk per thread and the chunks
+ * are evaluated concurrently with rayon; otherwise they are evaluated in one batch on the calling
+ * thread.
+ */
+use nalgebra::{DMatrix, DVector, RealField, SVector};
+
+use crate::convex::{DLazySet, DirectionMatrix, LazySet};
+use crate::error::SetError;
+
+/// Returns [`LazySet::try_support_batch`] for the columns of the matrix, evaluated in parallel
+/// with the `parallel` feature.
+pub fn try_support_batch<N, const D: usize>(
+    convex_set: &(dyn LazySet<N, D> + Sync),
+    directions: &DirectionMatrix<N, D>,
+) -> Vec<Result<(N, SVector<N, D>), SetError>>
+where
+    N: RealField,
+{
+    #[cfg(feature = "parallel")]
+    let results = {
+        use rayon::prelude::*;
+        chunks(directions.ncols())
+            .into_par_iter()
+            .map(|(start, len)| {
+                convex_set.try_support_batch(&directions.columns(start, len).into_owned())
+            })
+            .flatten()
+            .collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let results = convex_set.try_support_batch(directions);
+    results
+}
+
+/// Returns [`DLazySet::try_support_batch`] for the columns of the matrix, evaluated in parallel
+/// with the `parallel` feature.
+pub fn d_try_support_batch<N>(
+    convex_set: &(dyn DLazySet<N> + Sync),
+    directions: &DMatrix<N>,
+) -> Vec<Result<(N, DVector<N>), SetError>>
+where
+    N: RealField,
+{
+    #[cfg(feature = "parallel")]
+    let results = {
+        use rayon::prelude::*;
+        chunks(directions.ncols())
+            .into_par_iter()
+            .map(|(start, len)| {
+                convex_set.try_support_batch(&directions.columns(start, len).into_owned())
+            })
+            .flatten()
+            .collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let results = convex_set.try_support_batch(directions);
+    results
+}
+
+/// Splits `count` directions into one contiguous chunk of (start, length) per thread.
+#[cfg(feature = "parallel")]
+fn chunks(count: usize) -> Vec<(usize, usize)> {
+    let size = count.div_ceil(rayon::current_num_threads()).max(1);
+    (0..count)
+        .step_by(size)
+        .map(|start| (start, size.min(count - start)))
+        .collect()
+}
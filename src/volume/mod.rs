@@ -0,0 +1,441 @@
+/*!
+ * Volumes of convex sets.
+ *
+ * The exact volume of a polytope splits the convex hull of its vertices into pyramids over the
+ * facets with the centroid as apex, and measures the facets in the same way one dimension lower.
+ * Every level enumerates the facets of the points, so this is meant for low dimensions. Planar
+ * hulls are measured directly by the shoelace formula.
+ */
+use std::collections::HashSet;
+
+use nalgebra::{DMatrix, DVector, RealField, SVector};
+use num_traits::ToPrimitive;
+use rand::{distributions::uniform::SampleUniform, Rng};
+
+use crate::convex::{affine_hull, box_directions, facets, planar_hull, DirectionMatrix, LazySet};
+use crate::error::SetError;
+
+/// The volume of the convex hull of points by the pyramid decomposition of the module
+/// documentation. Hulls that are not full-dimensional have volume zero.
+pub(crate) fn hull_volume<N>(points: &[DVector<N>]) -> Result<N, SetError>
+where
+    N: RealField + Copy + ToPrimitive,
+{
+    let dimension = match points.first() {
+        Some(p) => p.len(),
+        None => return Ok(N::zero()),
+    };
+    if let Some(p) = points.iter().find(|p| p.len() != dimension) {
+        return Err(SetError::DimensionMismatch {
+            expected: dimension,
+            found: p.len(),
+        });
+    }
+    if dimension == 0 {
+        return Ok(N::one());
+    }
+    let hull = affine_hull(points);
+    if hull.basis.ncols() < dimension {
+        return Ok(N::zero());
+    }
+    if dimension == 1 {
+        let (low, high) = points
+            .iter()
+            .fold((points[0][0], points[0][0]), |(l, h), p| {
+                (l.min(p[0]), h.max(p[0]))
+            });
+        return Ok(high - low);
+    }
+    if dimension == 2 {
+        // the shoelace formula over the hull, which is the pyramid decomposition with the origin
+        // as apex
+        let vertices = planar_hull(points);
+        let two = N::one() + N::one();
+        let area = (0..vertices.len()).fold(N::zero(), |sum, i| {
+            let (p, q) = (vertices[i], vertices[(i + 1) % vertices.len()]);
+            sum + p[0] * q[1] - p[1] * q[0]
+        });
+        return Ok(area / two);
+    }
+
+    let (a_transform, upper_bounds) = facets(points)?;
+    let scale = points.iter().fold(N::zero(), |m, p| m.max(p.norm()));
+    let tol = N::default_epsilon().sqrt() * (N::one() + scale);
+    let mut seen = HashSet::new();
+    let mut volume = N::zero();
+    for (normal, b) in a_transform.row_iter().zip(upper_bounds.iter()) {
+        let norm = normal.norm();
+        if norm.is_zero() {
+            continue;
+        }
+        let (normal, b) = (normal.transpose() / norm, *b / norm);
+        let indices: Vec<usize> = (0..points.len())
+            .filter(|j| (normal.dot(&points[*j]) - b).abs() <= tol)
+            .collect();
+        // the enumeration may report a facet more than once up to rounding
+        if indices.len() < dimension || !seen.insert(indices.clone()) {
+            continue;
+        }
+        let facet: Vec<&DVector<N>> = indices.iter().map(|j| &points[*j]).collect();
+
+        // coordinates of the facet vertices in an orthonormal basis of the hyperplane, which
+        // makes the recursion go down one dimension
+        let basis = orthogonal_complement(&normal);
+        let coordinates: Vec<DVector<N>> = facet.iter().map(|p| basis.tr_mul(p)).collect();
+        let height = b - normal.dot(&hull.center);
+        let dimension_n: N = nalgebra::convert(dimension as f64);
+        volume += height * hull_volume(&coordinates)? / dimension_n;
+    }
+    Ok(volume)
+}
+
+/// An orthonormal basis of the vectors orthogonal to a unit vector, one per column.
+///
+/// The Householder reflection mapping the unit vector to a multiple of the coordinate axis it is
+/// largest along maps the other axes to the basis.
+fn orthogonal_complement<N>(unit: &DVector<N>) -> DMatrix<N>
+where
+    N: RealField + Copy,
+{
+    let dimension = unit.len();
+    let k = unit.iamax();
+    let mut w = unit.clone();
+    w[k] += unit[k].signum();
+    let scale = (N::one() + N::one()) / w.norm_squared();
+    let others: Vec<usize> = (0..dimension).filter(|i| *i != k).collect();
+    DMatrix::from_fn(dimension, others.len(), |i, j| {
+        let identity = if i == others[j] { N::one() } else { N::zero() };
+        identity - scale * w[i] * w[others[j]]
+    })
+}
+
+/// The volume of a ball of the given radius in the given dimension.
+pub(crate) fn ball_volume<N: RealField + Copy>(dimension: usize, radius: N) -> N {
+    if radius < N::zero() {
+        return N::zero();
+    }
+    // V_0 = 1, V_1 = 2 r and V_d = 2 pi r^2 / d V_{d-2}
+    let two = N::one() + N::one();
+    let mut volumes = [N::one(), two * radius];
+    for d in 2..=dimension {
+        let d_n: N = nalgebra::convert(d as f64);
+        volumes[d % 2] *= two * N::pi() * radius * radius / d_n;
+    }
+    volumes[dimension % 2]
+}
+
+/// The volume of the zonotope with the given generators, 2^d times the sum of |det| over all
+/// choices of d generators.
+pub(crate) fn zonotope_volume<N: RealField + Copy>(generators: &DMatrix<N>) -> N {
+    let (dimension, count) = generators.shape();
+    let two = N::one() + N::one();
+    let mut volume = N::zero();
+    if dimension > count {
+        return N::zero();
+    }
+    let mut chosen: Vec<usize> = (0..dimension).collect();
+    loop {
+        volume += generators.select_columns(chosen.iter()).determinant().abs();
+
+        // advance to the next choice in lexicographic order
+        let Some(i) = (0..dimension)
+            .rev()
+            .find(|i| chosen[*i] < count - dimension + i)
+        else {
+            break;
+        };
+        chosen[i] += 1;
+        for j in i + 1..dimension {
+            chosen[j] = chosen[j - 1] + 1;
+        }
+    }
+    volume * (0..dimension).fold(N::one(), |p, _| p * two)
+}
+
+/// A Monte-Carlo estimate of the volume of a set.
+#[derive(Copy, Clone, Debug)]
+pub struct VolumeEstimate<N> {
+    /// The estimated volume.
+    pub volume: N,
+    /// The volume of the bounding box the samples are drawn from.
+    pub box_volume: N,
+    /// The number of samples that lie in the set.
+    pub hits: usize,
+    /// The number of samples.
+    pub samples: usize,
+}
+
+impl<N> VolumeEstimate<N>
+where
+    N: RealField + Copy,
+{
+    /// The standard error of the estimated volume.
+    pub fn standard_error(&self) -> N {
+        if self.samples == 0 {
+            return self.box_volume;
+        }
+        let n: N = nalgebra::convert(self.samples as f64);
+        let p: N = nalgebra::convert(self.hits as f64 / self.samples as f64);
+        self.box_volume * (p * (N::one() - p) / n).sqrt()
+    }
+
+    /// The Wilson score interval of the volume for the standard normal quantile `z`, e.g. 1.96
+    /// for a 95% confidence interval.
+    ///
+    /// Unlike the interval of the standard error it stays within the bounding box and doesn't
+    /// collapse when none or all of the samples lie in the set.
+    pub fn confidence_interval(&self, z: N) -> (N, N) {
+        if self.samples == 0 {
+            return (N::zero(), self.box_volume);
+        }
+        let two = N::one() + N::one();
+        let n: N = nalgebra::convert(self.samples as f64);
+        let p: N = nalgebra::convert(self.hits as f64 / self.samples as f64);
+        let z2 = z * z;
+        let denominator = N::one() + z2 / n;
+        let center = (p + z2 / (two * n)) / denominator;
+        let half_width =
+            z / denominator * (p * (N::one() - p) / n + z2 / (two * two * n * n)).sqrt();
+        (
+            self.box_volume * (center - half_width).max(N::zero()),
+            self.box_volume * (center + half_width).min(N::one()),
+        )
+    }
+}
+
+/// Estimates the volume of a set from the fraction of uniform samples of its bounding box that
+/// lie in the set.
+///
+/// The bounding box is computed with one support query per face, so this works for any expression of
+/// lazy sets and can be compared to the exact volume of an overapproximation. Membership is
+/// tested with [`LazySet::is_member`], whose default reports points within a small tolerance of
+/// the set as members. Reports [`SetError::Unbounded`] for unbounded sets; the empty set has
+/// volume zero.
+pub fn estimate_volume<N, R, const D: usize>(
+    set: &dyn LazySet<N, D>,
+    num_samples: usize,
+    rng: &mut R,
+) -> Result<VolumeEstimate<N>, SetError>
+where
+    N: RealField + Copy + SampleUniform,
+    R: Rng + ?Sized,
+{
+    let directions = box_directions::<N>(D);
+    let mut rho = Vec::with_capacity(2 * D);
+    for support in set.try_support_batch(&DirectionMatrix::from_column_slice(directions.as_slice()))
+    {
+        match support {
+            Ok((value, _)) => rho.push(value),
+            Err(SetError::Infeasible) => {
+                return Ok(VolumeEstimate {
+                    volume: N::zero(),
+                    box_volume: N::zero(),
+                    hits: 0,
+                    samples: num_samples,
+                })
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    let high = SVector::<N, D>::from_fn(|i, _| rho[i]);
+    let low = SVector::<N, D>::from_fn(|i, _| -rho[i + D]);
+    let box_volume = (high - low).iter().fold(N::one(), |volume, w| volume * *w);
+
+    let mut hits = 0;
+    for _ in 0..num_samples {
+        let point = SVector::<N, D>::from_fn(|i, _| rng.gen_range(low[i]..=high[i]));
+        if set.is_member(&point) {
+            hits += 1;
+        }
+    }
+    let volume = if num_samples == 0 {
+        N::zero()
+    } else {
+        box_volume * nalgebra::convert(hits as f64 / num_samples as f64)
+    };
+    Ok(VolumeEstimate {
+        volume,
+        box_volume,
+        hits,
+        samples: num_samples,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{DMatrix, DVector, SVector, Vector2, Vector3, Vector4};
+
+    use crate::convex::{DHalfspacePolytope, DVPolytope, Hyperrectangle, Hypersphere};
+    use crate::overapproximate::{overapproximate_epsilon, overapproximate_with, Directions};
+
+    /// Unit directions spread evenly over the sphere on a Fibonacci spiral.
+    fn spiral_directions(count: usize) -> Directions<f64, 3> {
+        let golden_angle = std::f64::consts::PI * (3.0 - 5.0f64.sqrt());
+        Directions::Template(
+            (0..count)
+                .map(|i| {
+                    let z = 1.0 - (2 * i + 1) as f64 / count as f64;
+                    let r = (1.0 - z * z).sqrt();
+                    let angle = golden_angle * i as f64;
+                    Vector3::new(r * angle.cos(), r * angle.sin(), z)
+                })
+                .collect(),
+        )
+    }
+
+    /// The vertices of a box.
+    fn box_vertices(low: &[f64], high: &[f64]) -> Vec<DVector<f64>> {
+        (0..1usize << low.len())
+            .map(|corner| {
+                DVector::from_fn(low.len(), |i, _| {
+                    if corner & (1 << i) != 0 {
+                        high[i]
+                    } else {
+                        low[i]
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// The constraints x <= high and -x <= -low of a box.
+    fn box_constraints(low: &[f64], high: &[f64]) -> DHalfspacePolytope<f64> {
+        let dimension = low.len();
+        DHalfspacePolytope::new(
+            DMatrix::from_fn(2 * dimension, dimension, |i, j| match i {
+                _ if i == j => 1.0,
+                _ if i == j + dimension => -1.0,
+                _ => 0.0,
+            }),
+            DVector::from_fn(2 * dimension, |i, _| {
+                if i < dimension {
+                    high[i]
+                } else {
+                    -low[i - dimension]
+                }
+            }),
+        )
+    }
+
+    /// The vertices ±e_i of the cross-polytope.
+    fn cross_vertices(dimension: usize) -> Vec<DVector<f64>> {
+        (0..2 * dimension)
+            .map(|k| {
+                let sign = if k < dimension { 1.0 } else { -1.0 };
+                DVector::from_fn(
+                    dimension,
+                    |i, _| if i == k % dimension { sign } else { 0.0 },
+                )
+            })
+            .collect()
+    }
+
+    /// The vertices 0 and e_i of the standard simplex.
+    fn simplex_vertices(dimension: usize) -> Vec<DVector<f64>> {
+        (0..=dimension)
+            .map(|k| DVector::from_fn(dimension, |i, _| if i + 1 == k { 1.0 } else { 0.0 }))
+            .collect()
+    }
+
+    fn assert_volume(volume: f64, expected: f64) {
+        assert!(
+            (volume - expected).abs() <= 1e-9 * expected,
+            "{} != {}",
+            volume,
+            expected
+        );
+    }
+
+    #[test]
+    fn boxes() {
+        let (low, high) = ([-1.0, -0.5, 0.2], [1.0, 2.0, 0.7]);
+        let expected =
+            Hyperrectangle::from_bounds(Vector3::from(low), Vector3::from(high)).volume();
+        assert_volume(
+            DVPolytope::new(box_vertices(&low, &high)).volume().unwrap(),
+            expected,
+        );
+        assert_volume(box_constraints(&low, &high).volume().unwrap(), expected);
+
+        let (low, high) = ([-0.7; 4], [1.3; 4]);
+        let expected =
+            Hyperrectangle::from_bounds(Vector4::from(low), Vector4::from(high)).volume();
+        assert_volume(
+            DVPolytope::new(box_vertices(&low, &high)).volume().unwrap(),
+            expected,
+        );
+        assert_volume(box_constraints(&low, &high).volume().unwrap(), expected);
+    }
+
+    #[test]
+    fn cross_polytopes() {
+        for (dimension, expected) in [(3, 4.0 / 3.0), (4, 2.0 / 3.0)] {
+            let polytope = DVPolytope::new(cross_vertices(dimension));
+            assert_volume(polytope.volume().unwrap(), expected);
+            assert_volume(polytope.to_halfspace().unwrap().volume().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn simplices() {
+        for (dimension, expected) in [(3, 1.0 / 6.0), (4, 1.0 / 24.0)] {
+            let polytope = DVPolytope::new(simplex_vertices(dimension));
+            assert_volume(polytope.volume().unwrap(), expected);
+            assert_volume(polytope.to_halfspace().unwrap().volume().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn many_points_on_circle() {
+        let count = 3000;
+        let points = (0..count)
+            .map(|i| {
+                let angle = std::f64::consts::TAU * i as f64 / count as f64;
+                DVector::from_column_slice(&[angle.cos(), angle.sin()])
+            })
+            .collect();
+        let expected = count as f64 / 2.0 * (std::f64::consts::TAU / count as f64).sin();
+        let volume = DVPolytope::new(points).volume().unwrap();
+        assert!((volume - expected).abs() <= 1e-9, "{}", volume);
+    }
+
+    #[test]
+    fn fine_polygon_around_disk() {
+        let disk = Hypersphere::new(1.0, Vector2::zeros());
+        let polygon = overapproximate_epsilon(&disk, 1e-5).unwrap();
+        let volume = polygon.volume().unwrap();
+        assert!(volume >= std::f64::consts::PI, "{}", volume);
+        assert!(volume <= std::f64::consts::PI * (1.0 + 1e-4), "{}", volume);
+    }
+
+    #[test]
+    fn polytopes_around_ball() {
+        let ball_volume = 4.0 / 3.0 * std::f64::consts::PI;
+        for count in [50, 200] {
+            let ball = Hypersphere::new(1.0, SVector::<f64, 3>::zeros());
+            let polytope = overapproximate_with(&ball, &spiral_directions(count)).unwrap();
+            let volume = polytope.volume().unwrap();
+            assert!(volume > ball_volume, "{}: {}", count, volume);
+
+            // the volume doesn't depend on the scale of the constraints or the position
+            let scaled = DHalfspacePolytope::new(
+                DMatrix::from_fn(polytope.a_transform.nrows(), 3, |i, j| {
+                    polytope.a_transform[(i, j)] * 10f64.powi(i as i32 % 7 - 3)
+                }),
+                polytope
+                    .upper_bounds
+                    .map_with_location(|i, _, b| b * 10f64.powi(i as i32 % 7 - 3)),
+            );
+            let shifted = Hypersphere::new(1.0, Vector3::new(30.0, -20.0, 10.0));
+            let shifted = overapproximate_with(&shifted, &spiral_directions(count)).unwrap();
+            for other in [scaled.volume().unwrap(), shifted.volume().unwrap()] {
+                assert!(
+                    (other - volume).abs() <= 1e-9 * volume,
+                    "{}: {}",
+                    count,
+                    other
+                );
+            }
+        }
+    }
+}